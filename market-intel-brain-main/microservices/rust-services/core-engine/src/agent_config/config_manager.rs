@@ -2,6 +2,7 @@
 // Configuration Manager - Phase 21.5 Task C
 // مدير التكوين - المهمة 21.5 ج
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::path::Path;
 use tokio::sync::{RwLock, broadcast};
@@ -13,8 +14,14 @@ use super::config_types::{
     AgentConfigurationFile, AgentConfig, ConfigError, ConfigResult, 
     GlobalConfig, ValidationLevel
 };
-use super::config_watcher::{ConfigWatcher, WatcherConfig, ConfigWatcherResult};
-use super::events::{ConfigEvent, EventSource, ConfigUpdatePayload, AgentAdditionPayload};
+use super::config_watcher::{ConfigWatcher, WatcherConfig, ConfigWatcherResult, ReloadHook};
+use super::events::{
+    ConfigEvent, ConfigEventType, EventFilter, EventSource, ConfigUpdatePayload, AgentAdditionPayload
+};
+
+/// Buffer size of each filtered subscription channel
+/// حجم المخزن المؤقت لكل قناة اشتراك مصفاة
+const FILTERED_CHANNEL_CAPACITY: usize = 256;
 
 /// Configuration manager error
 /// خطأ مدير التكوين
@@ -71,6 +78,23 @@ pub struct ConfigManager {
     /// Update lock
     /// قفل التحديث
     update_lock: Arc<RwLock<bool>>,
+    
+    /// Operator enable/disable toggles, re-applied after reloads when runtime wins
+    /// تبديلات التفعيل من المشغل، يعاد تطبيقها بعد إعادة التحميل عندما تكون الأولوية لوقت التشغيل
+    runtime_overrides: Arc<parking_lot::RwLock<HashMap<String, bool>>>,
+}
+
+/// Which side wins when a file reload disagrees with an operator toggle
+/// أي جانب يفوز عندما تختلف إعادة تحميل الملف عن تبديل المشغل
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverridePrecedence {
+    /// The reloaded file replaces runtime toggles
+    /// الملف المعاد تحميله يحل محل تبديلات وقت التشغيل
+    FileWins,
+    
+    /// Runtime toggles are re-applied on top of each reload
+    /// يعاد تطبيق تبديلات وقت التشغيل فوق كل إعادة تحميل
+    RuntimeWins,
 }
 
 /// Manager configuration
@@ -108,6 +132,10 @@ pub struct ManagerConfig {
     /// Auto-save on changes
     /// الحفظ التلقائي عند التغييرات
     pub auto_save_on_changes: bool,
+    
+    /// Precedence between file reloads and `toggle_agent` calls
+    /// الأولوية بين إعادة تحميل الملف واستدعاءات `toggle_agent`
+    pub override_precedence: OverridePrecedence,
 }
 
 impl Default for ManagerConfig {
@@ -121,6 +149,7 @@ impl Default for ManagerConfig {
             max_agents: 1000,
             enable_config_locking: true,
             auto_save_on_changes: true,
+            override_precedence: OverridePrecedence::FileWins,
         }
     }
 }
@@ -141,7 +170,8 @@ impl ConfigManager {
         
         // Validate initial configuration
         if manager_config.enable_validation {
-            let validation_result = initial_config.validate();
+            let validation_result =
+                initial_config.validate_with_level(manager_config.validation_level.clone());
             if !validation_result.is_valid {
                 return Err(ConfigManagerError::ValidationFailed(validation_result.summary));
             }
@@ -159,10 +189,11 @@ impl ConfigManager {
             event_sender,
             manager_config,
             update_lock,
+            runtime_overrides: Arc::new(parking_lot::RwLock::new(HashMap::new())),
         };
         
         // Initialize watcher if hot reload is enabled
-        if manager_config.enable_hot_reload {
+        if manager.manager_config.enable_hot_reload {
             manager.initialize_watcher(config_file_path).await?;
         }
         
@@ -181,13 +212,31 @@ impl ConfigManager {
             backup_directory: None,
             enable_checksum_verification: true,
             backup_count: 5,
+            validation_level: if self.manager_config.enable_validation {
+                Some(self.manager_config.validation_level.clone())
+            } else {
+                Some(ValidationLevel::None)
+            },
         };
         
-        let (watcher, mut watcher_receiver) = ConfigWatcher::new(config_file_path, watcher_config)?;
+        let (mut watcher, mut watcher_receiver) = ConfigWatcher::new(config_file_path, watcher_config)?;
+        
+        // Re-apply operator toggles inside the reload itself, so a reloaded
+        // file never goes live (or reaches subscribers) with them undone
+        if self.manager_config.override_precedence == OverridePrecedence::RuntimeWins {
+            let runtime_overrides = self.runtime_overrides.clone();
+            let hook: ReloadHook = Arc::new(move |config: &mut AgentConfigurationFile| {
+                Self::apply_runtime_overrides(config, &runtime_overrides.read());
+            });
+            watcher = watcher.with_reload_hook(hook);
+        }
         
         // Start watcher
         watcher.start().await?;
         
+        // Share the watcher's configuration so file reloads are visible here
+        self.config = watcher.config_handle();
+        
         // Forward watcher events to manager's event channel
         let manager_sender = self.event_sender.clone();
         tokio::spawn(async move {
@@ -203,6 +252,70 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Re-apply operator toggles to a freshly loaded configuration
+    /// إعادة تطبيق تبديلات المشغل على تكوين محمل حديثاً
+    fn apply_runtime_overrides(config: &mut AgentConfigurationFile, overrides: &HashMap<String, bool>) {
+        for (name, enabled) in overrides.iter() {
+            if let Some(agent) = config.get_agent_mut(name) {
+                if agent.enabled != *enabled {
+                    debug!("Re-applying runtime override for '{}': enabled={}", name, enabled);
+                    agent.enabled = *enabled;
+                }
+            }
+        }
+    }
+
+    /// Subscribe to all configuration events
+    /// الاشتراك في جميع أحداث التكوين
+    pub fn subscribe(&self) -> broadcast::Receiver<ConfigEvent> {
+        self.event_sender.subscribe()
+    }
+
+    /// Subscribe to the events matching `filter`
+    /// الاشتراك في الأحداث المطابقة للمرشح
+    ///
+    /// A forwarding task is spawned per subscription and exits on the first
+    /// event, matching or not, after the returned receiver is dropped.
+    pub fn subscribe_filtered(&self, filter: EventFilter) -> broadcast::Receiver<ConfigEvent> {
+        let mut source = self.event_sender.subscribe();
+        let (sender, receiver) = broadcast::channel(FILTERED_CHANNEL_CAPACITY);
+        
+        tokio::spawn(async move {
+            loop {
+                let received = source.recv().await;
+                if sender.receiver_count() == 0 {
+                    // Subscriber went away
+                    break;
+                }
+                match received {
+                    Ok(event) => {
+                        if filter.matches(&event) {
+                            let _ = sender.send(event);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Filtered config subscription lagged, {} event(s) skipped", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        
+        receiver
+    }
+
+    /// Subscribe to the events of a single agent
+    /// الاشتراك في أحداث وكيل واحد
+    pub fn subscribe_agent(&self, name: &str) -> broadcast::Receiver<ConfigEvent> {
+        self.subscribe_filtered(EventFilter::new().with_agent_names(vec![name.to_string()]))
+    }
+
+    /// Operator toggles currently recorded
+    /// تبديلات المشغل المسجلة حاليا
+    pub async fn get_runtime_overrides(&self) -> HashMap<String, bool> {
+        self.runtime_overrides.read().clone()
+    }
+
     /// Get current configuration
     /// الحصول على التكوين الحالي
     pub async fn get_config(&self) -> AgentConfigurationFile {
@@ -378,10 +491,12 @@ impl ConfigManager {
         // Send event
         if self.manager_config.enable_event_broadcasting {
             let changed_fields = self.find_changed_fields(&previous_config, &agent);
+            let changes = previous_config.diff(&agent);
             let payload = ConfigUpdatePayload {
                 previous_config: Some(previous_config),
                 new_config: agent.clone(),
                 changed_fields,
+                changes,
                 reason,
                 update_source: "config_manager".to_string(),
             };
@@ -467,6 +582,8 @@ impl ConfigManager {
             *lock = false;
         }
         
+        self.runtime_overrides.write().remove(name);
+        
         info!("Agent '{}' removed successfully", name);
         Ok(())
     }
@@ -487,6 +604,8 @@ impl ConfigManager {
             format!("Toggled enabled from {} to {}", previous_enabled, enabled)
         ).await?;
         
+        self.runtime_overrides.write().insert(name.to_string(), enabled);
+        
         // Send toggle event
         if self.manager_config.enable_event_broadcasting {
             let event = ConfigEvent::agent_toggled(
@@ -652,7 +771,8 @@ impl ConfigManager {
     /// التحقق من التكوين الحالي
     pub async fn validate_current_config(&self) -> ConfigManagerResult<()> {
         let config = self.config.read().await;
-        let validation_result = config.validate();
+        let validation_result =
+            config.validate_with_level(self.manager_config.validation_level.clone());
         
         if !validation_result.is_valid {
             return Err(ConfigManagerError::ValidationFailed(validation_result.summary));
@@ -1013,6 +1133,164 @@ threshold = 2.5
         assert_eq!(agent.params.get("new_param"), Some(&serde_json::Value::String("test".to_string())));
     }
 
+    const GLOBAL_SECTION: &str = r#"
+[global]
+default_interval_ms = 1000
+max_concurrent_agents = 10
+operation_timeout_seconds = 30
+enable_hot_reload = true
+validation_level = "strict"
+"#;
+
+    fn agent_section(name: &str, version: &str) -> String {
+        format!(
+            r#"
+[[agents]]
+name = "{}"
+enabled = true
+interval_ms = 500
+description = "Test agent"
+version = "{}"
+author = "Test Author"
+
+[agents.risk]
+max_drawdown = 0.05
+max_position_value = 50000
+leverage_limit = 2.0
+
+[agents.monitoring]
+enable_metrics = true
+log_level = "info"
+performance_tracking = true
+"#,
+            name, version
+        )
+    }
+
+    fn agent_file(version: &str) -> String {
+        format!("{}{}", GLOBAL_SECTION, agent_section("TestAgent", version))
+    }
+
+    #[tokio::test]
+    async fn test_reload_honors_manager_validation_level() {
+        for (level, accepted) in [(ValidationLevel::Basic, true), (ValidationLevel::Strict, false)] {
+            let mut temp_file = NamedTempFile::new().unwrap();
+            temp_file.write_all(agent_file("1.0.0").as_bytes()).unwrap();
+
+            let manager_config = ManagerConfig {
+                validation_level: level.clone(),
+                ..ManagerConfig::default()
+            };
+            let (manager, _receiver) = ConfigManager::new(temp_file.path(), manager_config).await.unwrap();
+
+            // An empty version passes Basic but is an error under Strict
+            std::fs::write(temp_file.path(), agent_file("")).unwrap();
+            let result = manager.force_reload().await;
+            assert_eq!(result.is_ok(), accepted, "level {:?}", level);
+
+            let expected_version = if accepted { "" } else { "1.0.0" };
+            assert_eq!(manager.get_agent("TestAgent").await.unwrap().version, expected_version);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runtime_toggle_survives_reload_when_runtime_wins() {
+        for (precedence, expected_enabled) in [
+            (OverridePrecedence::RuntimeWins, false),
+            (OverridePrecedence::FileWins, true),
+        ] {
+            let mut temp_file = NamedTempFile::new().unwrap();
+            temp_file.write_all(agent_file("1.0.0").as_bytes()).unwrap();
+
+            let manager_config = ManagerConfig {
+                override_precedence: precedence,
+                ..ManagerConfig::default()
+            };
+            let (manager, _receiver) = ConfigManager::new(temp_file.path(), manager_config).await.unwrap();
+
+            manager.toggle_agent("TestAgent", false).await.unwrap();
+            let mut receiver = manager.subscribe();
+
+            // The file still says enabled = true
+            std::fs::write(temp_file.path(), agent_file("1.0.1")).unwrap();
+            manager.force_reload().await.unwrap();
+
+            let agent = manager.get_agent("TestAgent").await.unwrap();
+            assert_eq!(agent.version, "1.0.1");
+            assert_eq!(agent.enabled, expected_enabled, "precedence {:?}", precedence);
+
+            // Subscribers are told the same thing that went live
+            let mut reported = Vec::new();
+            while let Ok(Ok(event)) = tokio::time::timeout(std::time::Duration::from_millis(200), receiver.recv()).await {
+                if event.is_type(&ConfigEventType::UpdateConfig) && event.is_for_agent("TestAgent") {
+                    let payload: ConfigUpdatePayload = event.get_payload().unwrap();
+                    reported.push(payload.new_config.enabled);
+                }
+            }
+            assert!(!reported.is_empty(), "precedence {:?}", precedence);
+            assert!(reported.iter().all(|&enabled| enabled == expected_enabled), "precedence {:?}", precedence);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_agent_only_receives_that_agent() {
+        let content = format!(
+            "{}{}{}",
+            GLOBAL_SECTION,
+            agent_section("AgentA", "1.0.0"),
+            agent_section("AgentB", "1.0.0")
+        );
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(content.as_bytes()).unwrap();
+
+        let manager_config = ManagerConfig {
+            enable_hot_reload: false,
+            ..ManagerConfig::default()
+        };
+        let (manager, _receiver) = ConfigManager::new(temp_file.path(), manager_config).await.unwrap();
+        let mut agent_a_events = manager.subscribe_agent("AgentA");
+
+        manager.toggle_agent("AgentB", false).await.unwrap();
+        manager.toggle_agent("AgentA", false).await.unwrap();
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(1), agent_a_events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(event.is_for_agent("AgentA"));
+        while let Ok(Ok(event)) =
+            tokio::time::timeout(std::time::Duration::from_millis(50), agent_a_events.recv()).await
+        {
+            assert!(event.is_for_agent("AgentA"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filtered_forwarder_exits_on_unmatched_events_after_drop() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(agent_file("1.0.0").as_bytes()).unwrap();
+
+        let manager_config = ManagerConfig {
+            enable_hot_reload: false,
+            ..ManagerConfig::default()
+        };
+        let (manager, _receiver) = ConfigManager::new(temp_file.path(), manager_config).await.unwrap();
+        let baseline = manager.event_sender.receiver_count();
+
+        drop(manager.subscribe_agent("SomeOtherAgent"));
+        assert_eq!(manager.event_sender.receiver_count(), baseline + 1);
+
+        // None of these match the filter, the forwarder must still notice
+        manager.toggle_agent("TestAgent", false).await.unwrap();
+        for _ in 0..100 {
+            if manager.event_sender.receiver_count() == baseline {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(manager.event_sender.receiver_count(), baseline);
+    }
+
     #[test]
     fn test_manager_config() {
         let config = ManagerConfig::default();
@@ -1024,5 +1302,6 @@ threshold = 2.5
         assert_eq!(config.max_agents, 1000);
         assert!(config.enable_config_locking);
         assert!(config.auto_save_on_changes);
+        assert_eq!(config.override_precedence, OverridePrecedence::FileWins);
     }
 }
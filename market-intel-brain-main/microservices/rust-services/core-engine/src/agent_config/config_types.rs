@@ -4,6 +4,7 @@
 
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
 use chrono::{DateTime, Utc};
 use thiserror::Error;
 use uuid::Uuid;
@@ -67,9 +68,22 @@ pub enum ValidationLevel {
     Comprehensive,
 }
 
+impl ValidationLevel {
+    /// Level name as written in configuration files
+    /// اسم المستوى كما هو مكتوب في ملفات التكوين
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationLevel::None => "none",
+            ValidationLevel::Basic => "basic",
+            ValidationLevel::Strict => "strict",
+            ValidationLevel::Comprehensive => "comprehensive",
+        }
+    }
+}
+
 /// Agent configuration
 /// تكوين الوكيل
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AgentConfig {
     /// Agent name
     /// اسم الوكيل
@@ -97,6 +111,7 @@ pub struct AgentConfig {
     
     /// Agent-specific parameters
     /// معلمات خاصة بالوكيل
+    #[serde(default)]
     pub params: HashMap<String, serde_json::Value>,
     
     /// Risk management configuration
@@ -109,12 +124,13 @@ pub struct AgentConfig {
     
     /// Configuration metadata
     /// بيانات وصفية التكوين
+    #[serde(default)]
     pub metadata: AgentMetadata,
 }
 
 /// Risk management configuration
 /// تكوين إدارة المخاطر
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RiskConfig {
     /// Maximum drawdown allowed (0.0 to 1.0)
     /// أقصى انخفاض مسموح به (0.0 إلى 1.0)
@@ -130,6 +146,7 @@ pub struct RiskConfig {
     
     /// Additional risk parameters
     /// معلمات المخاطر الإضافية
+    #[serde(default)]
     pub additional_params: HashMap<String, serde_json::Value>,
 }
 
@@ -146,7 +163,7 @@ impl Default for RiskConfig {
 
 /// Monitoring configuration
 /// تكوين المراقبة
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MonitoringConfig {
     /// Enable metrics collection
     /// تمكين جمع المقاييس
@@ -162,6 +179,7 @@ pub struct MonitoringConfig {
     
     /// Additional monitoring parameters
     /// معلمات المراقبة الإضافية
+    #[serde(default)]
     pub additional_params: HashMap<String, serde_json::Value>,
 }
 
@@ -204,7 +222,7 @@ pub enum LogLevel {
 
 /// Agent metadata
 /// بيانات وصفية الوكيل
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AgentMetadata {
     /// Configuration creation timestamp
     /// وقت إنشاء التكوين
@@ -267,6 +285,17 @@ impl Default for AgentConfigurationFile {
     }
 }
 
+/// One fragment of a configuration directory; both sections are optional
+/// جزء واحد من دليل التكوين؛ كلا القسمين اختياريان
+#[derive(Debug, Deserialize)]
+struct ConfigFragment {
+    #[serde(default)]
+    global: Option<GlobalConfig>,
+    
+    #[serde(default)]
+    agents: Vec<AgentConfig>,
+}
+
 /// Configuration validation result
 /// نتيجة التحقق من التكوين
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -352,6 +381,23 @@ pub enum ErrorSeverity {
     Critical,
 }
 
+/// A single changed field between two agent configurations
+/// حقل واحد متغير بين تكويني وكيل
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Dotted field path, e.g. `risk.max_drawdown` or `params.threshold`
+    /// مسار الحقل المنقط
+    pub field: String,
+    
+    /// Previous value (`null` if the field was added)
+    /// القيمة السابقة
+    pub old: serde_json::Value,
+    
+    /// New value (`null` if the field was removed)
+    /// القيمة الجديدة
+    pub new: serde_json::Value,
+}
+
 /// Configuration error
 /// خطأ التكوين
 #[derive(Error, Debug)]
@@ -554,6 +600,37 @@ impl AgentConfig {
         }
     }
 
+    /// Field-level differences from `self` to `other`
+    /// الفروق على مستوى الحقول من `self` إلى `other`
+    ///
+    /// Nested tables (`params`, `risk`, `monitoring`, ...) are walked so each
+    /// leaf gets its own entry. `metadata` is bookkeeping that changes on every
+    /// update and is left out.
+    pub fn diff(&self, other: &AgentConfig) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        let (old, new) = match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(old), Ok(new)) => (old, new),
+            _ => return changes,
+        };
+        
+        if let (serde_json::Value::Object(old), serde_json::Value::Object(new)) = (old, new) {
+            let keys: std::collections::BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                if key == "metadata" {
+                    continue;
+                }
+                diff_values(
+                    key,
+                    old.get(key).unwrap_or(&serde_json::Value::Null),
+                    new.get(key).unwrap_or(&serde_json::Value::Null),
+                    &mut changes,
+                );
+            }
+        }
+        
+        changes
+    }
+
     /// Calculate configuration checksum
     /// حساب المجموع الاختباري للتكوين
     pub fn calculate_checksum(&self) -> String {
@@ -640,24 +717,110 @@ impl AgentConfig {
     }
 }
 
+/// Recursively collect leaf differences under `path`
+/// جمع فروق الأوراق بشكل متكرر تحت `path`
+fn diff_values(
+    path: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    changes: &mut Vec<FieldChange>,
+) {
+    if old == new {
+        return;
+    }
+    
+    match (old, new) {
+        (serde_json::Value::Object(old_map), serde_json::Value::Object(new_map)) => {
+            let keys: std::collections::BTreeSet<&String> =
+                old_map.keys().chain(new_map.keys()).collect();
+            for key in keys {
+                diff_values(
+                    &format!("{}.{}", path, key),
+                    old_map.get(key).unwrap_or(&serde_json::Value::Null),
+                    new_map.get(key).unwrap_or(&serde_json::Value::Null),
+                    changes,
+                );
+            }
+        }
+        _ => changes.push(FieldChange {
+            field: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+/// On-disk format of a configuration file, chosen by extension
+/// صيغة ملف التكوين على القرص، تُحدد حسب الامتداد
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Detect the format from a path's extension; unknown or missing extensions are TOML
+    /// اكتشاف الصيغة من امتداد المسار؛ الامتدادات غير المعروفة تُعامل كـ TOML
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            Some("json") => ConfigFormat::Json,
+            _ => ConfigFormat::Toml,
+        }
+    }
+
+    /// Canonical file extension for this format
+    /// امتداد الملف القياسي لهذه الصيغة
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Yaml => "yaml",
+            ConfigFormat::Json => "json",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "TOML",
+            ConfigFormat::Yaml => "YAML",
+            ConfigFormat::Json => "JSON",
+        }
+    }
+}
+
 impl AgentConfigurationFile {
-    /// Load configuration from file
-    /// تحميل التكوين من الملف
+    /// Load configuration from file, picking TOML, YAML or JSON by extension
+    /// تحميل التكوين من الملف باختيار TOML أو YAML أو JSON حسب الامتداد
     pub fn load_from_file(file_path: &str) -> ConfigResult<Self> {
         let content = std::fs::read_to_string(file_path)
             .map_err(|e| ConfigError::FileNotFound(format!("Cannot read file {}: {}", file_path, e)))?;
         
-        let config: AgentConfigurationFile = toml::from_str(&content)
-            .map_err(|e| ConfigError::ParseError(format!("Cannot parse TOML: {}", e)))?;
+        Self::from_str_with_format(&content, ConfigFormat::from_path(file_path))
+    }
+
+    /// Parse configuration content in the given format
+    /// تحليل محتوى التكوين بالصيغة المحددة
+    pub fn from_str_with_format(content: &str, format: ConfigFormat) -> ConfigResult<Self> {
+        let parsed = match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        };
         
-        Ok(config)
+        parsed.map_err(|e| ConfigError::ParseError(format!("Cannot parse {}: {}", format.name(), e)))
     }
 
-    /// Save configuration to file
-    /// حفظ التكوين في الملف
+    /// Save configuration to file in the format matching its extension
+    /// حفظ التكوين في الملف بالصيغة المطابقة لامتداده
     pub fn save_to_file(&self, file_path: &str) -> ConfigResult<()> {
-        let content = toml::to_string_pretty(self)
-            .map_err(|e| ConfigError::SerializationError(toml::de::Error::custom(e.to_string())))?;
+        let content = self.to_string_with_format(ConfigFormat::from_path(file_path))?;
         
         std::fs::write(file_path, content)
             .map_err(ConfigError::IoError)?;
@@ -665,9 +828,112 @@ impl AgentConfigurationFile {
         Ok(())
     }
 
-    /// Validate the entire configuration
-    /// التحقق من التكوين بأكمله
+    /// Serialize configuration in the given format
+    /// تسلسل التكوين بالصيغة المحددة
+    pub fn to_string_with_format(&self, format: ConfigFormat) -> ConfigResult<String> {
+        let serialized = match format {
+            ConfigFormat::Toml => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::to_string(self).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+        };
+        
+        serialized.map_err(|e| ConfigError::SerializationError(toml::de::Error::custom(e)))
+    }
+
+    /// Load and merge every `*.toml` fragment under `dir` (recursively, in path order).
+    /// At most one fragment may carry `[global]`; an agent name defined in two
+    /// fragments is a `DuplicateAgentName` error naming both files.
+    /// تحميل ودمج جميع أجزاء `*.toml` داخل الدليل؛ تكرار اسم الوكيل عبر ملفين يُعد تعارضًا
+    pub fn load_from_directory<P: AsRef<std::path::Path>>(dir: P) -> ConfigResult<Self> {
+        let mut fragments = Vec::new();
+        for path in Self::fragment_paths(dir.as_ref())? {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| ConfigError::FileNotFound(format!("Cannot read file {}: {}", path.display(), e)))?;
+            fragments.push((path, content));
+        }
+        
+        Self::from_fragments(&fragments)
+    }
+
+    /// Merge already-read `(path, content)` fragments with the rules of `load_from_directory`
+    /// دمج أجزاء مقروءة مسبقًا بنفس قواعد `load_from_directory`
+    pub fn from_fragments(fragments: &[(std::path::PathBuf, String)]) -> ConfigResult<Self> {
+        let mut merged = AgentConfigurationFile::default();
+        let mut global_source: Option<std::path::PathBuf> = None;
+        let mut agent_sources: HashMap<String, std::path::PathBuf> = HashMap::new();
+        
+        for (path, content) in fragments {
+            let fragment: ConfigFragment = toml::from_str(content)
+                .map_err(|e| ConfigError::ParseError(format!("Cannot parse TOML in {}: {}", path.display(), e)))?;
+            
+            if let Some(global) = fragment.global {
+                if let Some(previous) = &global_source {
+                    return Err(ConfigError::InvalidConfiguration(format!(
+                        "[global] is defined in both {} and {}",
+                        previous.display(),
+                        path.display()
+                    )));
+                }
+                merged.global = global;
+                global_source = Some(path.clone());
+            }
+            
+            for agent in fragment.agents {
+                if let Some(previous) = agent_sources.get(&agent.name) {
+                    return Err(ConfigError::DuplicateAgentName(format!(
+                        "{} (defined in {} and {})",
+                        agent.name,
+                        previous.display(),
+                        path.display()
+                    )));
+                }
+                agent_sources.insert(agent.name.clone(), path.clone());
+                merged.agents.push(agent);
+            }
+        }
+        
+        Ok(merged)
+    }
+
+    /// All `*.toml` fragment files under `dir`, sorted by path
+    /// جميع ملفات أجزاء `*.toml` داخل الدليل مرتبة حسب المسار
+    pub fn fragment_paths(dir: &std::path::Path) -> ConfigResult<Vec<std::path::PathBuf>> {
+        let mut paths = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().and_then(|e| e.to_str()) == Some("toml") {
+                    paths.push(path);
+                }
+            }
+        }
+        
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Validate the entire configuration at the file's own `global.validation_level`
+    /// التحقق من التكوين بأكمله بمستوى التحقق المحدد في الملف
     pub fn validate(&self) -> ConfigValidationResult {
+        self.validate_with_level(self.global.validation_level.clone())
+    }
+
+    /// Validate the entire configuration at an explicit level
+    /// التحقق من التكوين بأكمله بمستوى محدد
+    pub fn validate_with_level(&self, validation_level: ValidationLevel) -> ConfigValidationResult {
+        if validation_level == ValidationLevel::None {
+            return ConfigValidationResult {
+                is_valid: true,
+                errors: Vec::new(),
+                warnings: Vec::new(),
+                summary: "Configuration validation skipped".to_string(),
+            };
+        }
+
         let mut all_errors = Vec::new();
         let mut all_warnings = Vec::new();
 
@@ -705,7 +971,7 @@ impl AgentConfigurationFile {
 
         // Validate each agent
         for agent in &self.agents {
-            let validation_result = agent.validate(self.global.validation_level.clone());
+            let validation_result = agent.validate(validation_level.clone());
             all_errors.extend(validation_result.errors);
             all_warnings.extend(validation_result.warnings);
         }
@@ -868,6 +1134,70 @@ mod tests {
         assert_eq!(retrieved.unwrap().name, "TestAgent");
     }
 
+    fn round_trip_fixture() -> AgentConfigurationFile {
+        let mut config = AgentConfigurationFile::default();
+        let mut agent = AgentConfig::new("RoundTripAgent".to_string());
+        agent.enabled = true;
+        agent.interval_ms = 250;
+        agent.params.insert("threshold".to_string(), serde_json::json!(2.5));
+        agent.params.insert("symbols".to_string(), serde_json::json!(["AAPL", "MSFT"]));
+        config.add_agent(agent).unwrap();
+        config.add_agent(AgentConfig::new("IdleAgent".to_string())).unwrap();
+        config
+    }
+
+    fn assert_round_trip(extension: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("agents.{}", extension));
+        let path = path.to_str().unwrap();
+        let original = round_trip_fixture();
+
+        original.save_to_file(path).unwrap();
+        let loaded = AgentConfigurationFile::load_from_file(path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&original).unwrap(),
+            "round trip through .{} changed the configuration",
+            extension
+        );
+    }
+
+    #[test]
+    fn test_format_detection() {
+        assert_eq!(ConfigFormat::from_path("agents.toml"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("agents.yaml"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("agents.YML"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("agents.json"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("agents.conf"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("agents"), ConfigFormat::Toml);
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        assert_round_trip("toml");
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        assert_round_trip("yaml");
+        assert_round_trip("yml");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        assert_round_trip("json");
+    }
+
+    #[test]
+    fn test_yaml_content_is_yaml() {
+        let content = round_trip_fixture()
+            .to_string_with_format(ConfigFormat::Yaml)
+            .unwrap();
+        assert!(content.contains("agents:"));
+        assert!(toml::from_str::<AgentConfigurationFile>(&content).is_err());
+    }
+
     #[test]
     fn test_validation_levels() {
         let agent = AgentConfig::new("TestAgent".to_string());
@@ -880,4 +1210,86 @@ mod tests {
         assert!(comprehensive_result.warnings.len() >= strict_result.warnings.len());
         assert!(strict_result.warnings.len() >= basic_result.warnings.len());
     }
+
+    #[test]
+    fn test_file_validation_with_explicit_level() {
+        let mut config = AgentConfigurationFile::default();
+        let mut agent = AgentConfig::new("TestAgent".to_string());
+        agent.version = String::new();
+        config.add_agent(agent).unwrap();
+
+        // An empty version is only an error from Strict upwards
+        assert!(!config.validate_with_level(ValidationLevel::Strict).is_valid);
+        assert!(config.validate_with_level(ValidationLevel::Basic).is_valid);
+        assert!(config.validate_with_level(ValidationLevel::None).is_valid);
+    }
+
+    #[test]
+    fn test_diff_scalar_field() {
+        let before = AgentConfig::new("TestAgent".to_string());
+        let mut after = before.clone();
+        after.interval_ms = 250;
+        after.metadata.checksum = "changed".to_string();
+
+        assert_eq!(
+            before.diff(&after),
+            vec![FieldChange {
+                field: "interval_ms".to_string(),
+                old: serde_json::json!(1000),
+                new: serde_json::json!(250),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_params_map() {
+        let mut before = AgentConfig::new("TestAgent".to_string());
+        before.params.insert("threshold".to_string(), serde_json::json!(2.5));
+        before.params.insert("window".to_string(), serde_json::json!(20));
+        let mut after = before.clone();
+        after.params.insert("threshold".to_string(), serde_json::json!(3.0));
+        after.params.remove("window");
+        after.params.insert("mode".to_string(), serde_json::json!("fast"));
+
+        let changes = before.diff(&after);
+        assert_eq!(changes.len(), 3);
+        assert!(changes.contains(&FieldChange {
+            field: "params.threshold".to_string(),
+            old: serde_json::json!(2.5),
+            new: serde_json::json!(3.0),
+        }));
+        assert!(changes.contains(&FieldChange {
+            field: "params.window".to_string(),
+            old: serde_json::json!(20),
+            new: serde_json::Value::Null,
+        }));
+        assert!(changes.contains(&FieldChange {
+            field: "params.mode".to_string(),
+            old: serde_json::Value::Null,
+            new: serde_json::json!("fast"),
+        }));
+    }
+
+    #[test]
+    fn test_diff_nested_risk_config() {
+        let before = AgentConfig::new("TestAgent".to_string());
+        let mut after = before.clone();
+        after.risk.max_drawdown = 0.1;
+        after
+            .risk
+            .additional_params
+            .insert("var_limit".to_string(), serde_json::json!(0.02));
+
+        let changes = before.diff(&after);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["risk.additional_params.var_limit", "risk.max_drawdown"]);
+        assert_eq!(changes[1].old, serde_json::json!(0.05));
+        assert_eq!(changes[1].new, serde_json::json!(0.1));
+    }
+
+    #[test]
+    fn test_diff_identical_configs() {
+        let agent = AgentConfig::new("TestAgent".to_string());
+        assert!(agent.diff(&agent.clone()).is_empty());
+    }
 }
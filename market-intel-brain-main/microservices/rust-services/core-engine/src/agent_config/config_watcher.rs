@@ -13,9 +13,66 @@ use notify::event::ModifyKind;
 use thiserror::Error;
 use chrono::Utc;
 
-use super::config_types::{AgentConfigurationFile, ConfigError, ConfigResult};
+use super::config_types::{AgentConfigurationFile, ConfigError, ConfigResult, ValidationLevel};
 use super::events::{ConfigEvent, EventSource, ReloadCompletionPayload, ReloadFailedPayload};
 
+/// Contents of the watched file, or of every fragment in the watched directory,
+/// read in one pass so that hashing, size checks and parsing see the same bytes
+/// محتويات الملف المراقب أو أجزاء الدليل المراقب مقروءة مرة واحدة
+#[derive(Debug, Clone, PartialEq)]
+struct SourceSnapshot {
+    is_directory: bool,
+    files: Vec<(PathBuf, String)>,
+}
+
+impl SourceSnapshot {
+    fn read(path: &Path) -> ConfigWatcherResult<Self> {
+        let is_directory = path.is_dir();
+        let paths = if is_directory {
+            AgentConfigurationFile::fragment_paths(path)?
+        } else {
+            vec![path.to_path_buf()]
+        };
+        
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let content = std::fs::read_to_string(&path)?;
+            files.push((path, content));
+        }
+        Ok(Self { is_directory, files })
+    }
+
+    /// A directory hashes every fragment's path and contents
+    /// الدليل يجمع مسار ومحتوى كل جزء
+    fn checksum(&self) -> String {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        
+        let mut hasher = DefaultHasher::new();
+        for (path, content) in &self.files {
+            if self.is_directory {
+                path.hash(&mut hasher);
+            }
+            hasher.write(content.as_bytes());
+        }
+        format!("{:x}", hasher.finish())
+    }
+
+    fn size(&self) -> u64 {
+        self.files.iter().map(|(_, content)| content.len() as u64).sum()
+    }
+
+    fn parse(&self) -> ConfigResult<AgentConfigurationFile> {
+        match (self.is_directory, self.files.first()) {
+            (false, Some((path, content))) => AgentConfigurationFile::from_str_with_format(
+                content,
+                super::config_types::ConfigFormat::from_path(path),
+            ),
+            _ => AgentConfigurationFile::from_fragments(&self.files),
+        }
+    }
+}
+
 /// Configuration watcher error
 /// خطأ مراقب التكوين
 #[derive(Error, Debug)]
@@ -43,12 +100,19 @@ pub enum ConfigWatcherError {
     
     #[error("Invalid file path: {0}")]
     InvalidFilePath(String),
+    
+    #[error("File still changing after {attempts} reads: {path}")]
+    FileUnstable { path: String, attempts: u32 },
 }
 
 /// Result type for config watcher operations
 /// نوع النتيجة لعمليات مراقب التكوين
 pub type ConfigWatcherResult<T> = Result<T, ConfigWatcherError>;
 
+/// Adjusts a validated configuration before it is installed and diffed
+/// يعدّل التكوين بعد التحقق منه وقبل تثبيته ومقارنته
+pub type ReloadHook = Arc<dyn Fn(&mut AgentConfigurationFile) + Send + Sync>;
+
 /// Configuration watcher
 /// مراقب التكوين
 pub struct ConfigWatcher {
@@ -75,6 +139,10 @@ pub struct ConfigWatcher {
     /// Statistics
     /// الإحصائيات
     statistics: Arc<RwLock<WatcherStatistics>>,
+    
+    /// Applied to every loaded configuration before it replaces the current one
+    /// يطبق على كل تكوين محمل قبل أن يحل محل التكوين الحالي
+    reload_hook: Option<ReloadHook>,
 }
 
 /// Watcher configuration
@@ -112,6 +180,22 @@ pub struct WatcherConfig {
     /// Number of backup files to keep
     /// عدد ملفات النسخ الاحتياطي للاحتفاظ بها
     pub backup_count: usize,
+    
+    /// Validation level for reloaded files; `None` uses the file's `global.validation_level`
+    /// مستوى التحقق للملفات المعاد تحميلها؛ `None` يستخدم مستوى الملف نفسه
+    pub validation_level: Option<ValidationLevel>,
+    
+    /// Treat the watched path as a directory of `*.toml` fragments merged into one file
+    /// معاملة المسار المراقب كدليل لأجزاء `*.toml` تُدمج في ملف واحد
+    pub watch_directory: bool,
+    
+    /// Delay between the two reads that must agree before a changed file is parsed; 0 disables
+    /// التأخير بين قراءتين يجب أن تتطابقا قبل تحليل الملف المتغير؛ 0 يعطل الخاصية
+    pub stabilization_delay_ms: u64,
+    
+    /// Maximum reads while waiting for a file to stop changing
+    /// أقصى عدد من القراءات أثناء انتظار توقف الملف عن التغير
+    pub stabilization_max_attempts: u32,
 }
 
 impl Default for WatcherConfig {
@@ -125,6 +209,10 @@ impl Default for WatcherConfig {
             backup_directory: None,
             enable_checksum_verification: true,
             backup_count: 5,
+            validation_level: None,
+            watch_directory: false,
+            stabilization_delay_ms: 50,
+            stabilization_max_attempts: 10,
         }
     }
 }
@@ -182,7 +270,8 @@ impl ConfigWatcher {
             ));
         }
         
-        if !file_path.is_file() {
+        let expected_kind = if config.watch_directory { file_path.is_dir() } else { file_path.is_file() };
+        if !expected_kind {
             return Err(ConfigWatcherError::InvalidFilePath(
                 file_path.to_string_lossy().to_string()
             ));
@@ -192,9 +281,7 @@ impl ConfigWatcher {
         let (event_sender, event_receiver) = broadcast::channel(1000);
         
         // Load initial configuration
-        let initial_config = AgentConfigurationFile::load_from_file(
-            file_path.to_string_lossy().as_ref()
-        )?;
+        let initial_config = Self::load_source(&file_path, &config)?;
         
         let current_config = Arc::new(RwLock::new(initial_config));
         
@@ -205,11 +292,20 @@ impl ConfigWatcher {
             watcher_handle: Arc::new(RwLock::new(None)),
             config,
             statistics: Arc::new(RwLock::new(WatcherStatistics::default())),
+            reload_hook: None,
         };
         
         Ok((watcher, event_receiver))
     }
 
+    /// Run `hook` on each loaded configuration before it is installed, so
+    /// subscribers only ever see the adjusted result. Set it before `start`.
+    /// تشغيل الخطاف على كل تكوين محمل قبل تثبيته؛ يجب ضبطه قبل `start`
+    pub fn with_reload_hook(mut self, hook: ReloadHook) -> Self {
+        self.reload_hook = Some(hook);
+        self
+    }
+
     /// Start watching for configuration changes
     /// بدء مراقبة تغييرات التكوين
     pub async fn start(&self) -> ConfigWatcherResult<()> {
@@ -272,6 +368,12 @@ impl ConfigWatcher {
         self.current_config.read().await
     }
 
+    /// Shared handle to the live configuration
+    /// مقبض مشترك للتكوين الحي
+    pub fn config_handle(&self) -> Arc<RwLock<AgentConfigurationFile>> {
+        self.current_config.clone()
+    }
+
     /// Force reload configuration
     /// إعادة تحميل التكوين بالقوة
     pub async fn force_reload(&self) -> ConfigWatcherResult<()> {
@@ -301,12 +403,13 @@ impl ConfigWatcher {
     /// Load initial configuration
     /// تحميل التكوين الأولي
     async fn load_initial_config(&self) -> ConfigWatcherResult<()> {
-        let config = AgentConfigurationFile::load_from_file(
-            self.file_path.to_string_lossy().as_ref()
-        )?;
+        let mut config = Self::load_source(&self.file_path, &self.config)?;
+        if let Some(hook) = &self.reload_hook {
+            hook(&mut config);
+        }
         
         // Validate configuration
-        let validation_result = config.validate();
+        let validation_result = config.validate_with_level(self.effective_validation_level(&config));
         if !validation_result.is_valid {
             warn!("Initial configuration validation failed: {}", validation_result.summary);
         }
@@ -330,6 +433,32 @@ impl ConfigWatcher {
         Ok(())
     }
 
+    /// Load the watched file, or merge the watched directory's fragments
+    /// تحميل الملف المراقب أو دمج أجزاء الدليل المراقب
+    fn load_source(path: &Path, config: &WatcherConfig) -> ConfigResult<AgentConfigurationFile> {
+        if config.watch_directory {
+            AgentConfigurationFile::load_from_directory(path)
+        } else {
+            AgentConfigurationFile::load_from_file(path.to_string_lossy().as_ref())
+        }
+    }
+
+    /// Validation level applied to `config`
+    /// مستوى التحقق المطبق على `config`
+    fn effective_validation_level(&self, config: &AgentConfigurationFile) -> ValidationLevel {
+        Self::validation_level_for(&self.config, config)
+    }
+
+    fn validation_level_for(
+        watcher_config: &WatcherConfig,
+        config: &AgentConfigurationFile,
+    ) -> ValidationLevel {
+        watcher_config
+            .validation_level
+            .clone()
+            .unwrap_or_else(|| config.global.validation_level.clone())
+    }
+
     /// Start file watcher using notify crate
     /// بدء مراقب الملفات باستخدام صندوق notify
     async fn start_file_watcher(&self) -> ConfigWatcherResult<()> {
@@ -356,25 +485,33 @@ impl ConfigWatcher {
             NotifyConfig::default(),
         )?;
         
-        // Watch the file
-        watcher.watch(&file_path, RecursiveMode::NonRecursive)?;
+        // Watch the file, or the whole fragment tree in directory mode
+        let recursive_mode = if self.config.watch_directory {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&file_path, recursive_mode)?;
         
         // Clone necessary data for the task
         let current_config = self.current_config.clone();
         let event_sender = self.event_sender.clone();
         let statistics = self.statistics.clone();
         let config = self.config.clone();
+        let reload_hook = self.reload_hook.clone();
         let file_path_clone = file_path.clone();
         
         // Spawn watcher task
         let task = tokio::spawn(async move {
+            // The notify watcher stops delivering events once dropped
+            let _watcher = watcher;
             let mut last_change_time = None;
             
             while let Ok(event) = rx.recv() {
                 debug!("File system event received: {:?}", event);
                 
                 // Filter for relevant events
-                if !self.is_relevant_event(&event) {
+                if !Self::is_relevant_event(&event, &config) {
                     continue;
                 }
                 
@@ -391,12 +528,13 @@ impl ConfigWatcher {
                 last_change_time = Some(now);
                 
                 // Reload configuration
-                if let Err(e) = Self::reload_config_internal(
+                if let Err(e) = Self::reload_if_changed(
                     &file_path_clone,
                     &current_config,
                     &event_sender,
                     &statistics,
                     &config,
+                    reload_hook.as_ref(),
                 ).await {
                     error!("Failed to reload configuration: {}", e);
                 }
@@ -420,6 +558,7 @@ impl ConfigWatcher {
         let event_sender = self.event_sender.clone();
         let statistics = self.statistics.clone();
         let config = self.config.clone();
+        let reload_hook = self.reload_hook.clone();
         let polling_interval = Duration::from_secs(config.polling_interval_seconds);
         
         // Spawn polling task
@@ -440,6 +579,7 @@ impl ConfigWatcher {
                                     &event_sender,
                                     &statistics,
                                     &config,
+                                    reload_hook.as_ref(),
                                 ).await {
                                     error!("Failed to reload configuration: {}", e);
                                 }
@@ -467,7 +607,14 @@ impl ConfigWatcher {
 
     /// Check if event is relevant for configuration changes
     /// التحقق مما إذا كان الحدث ذا صلة لتغييرات التكوين
-    fn is_relevant_event(&self, event: &Event) -> bool {
+    fn is_relevant_event(event: &Event, config: &WatcherConfig) -> bool {
+        // In directory mode only changes to fragments matter
+        if config.watch_directory
+            && !event.paths.iter().any(|p| p.extension().and_then(|e| e.to_str()) == Some("toml"))
+        {
+            return false;
+        }
+        
         match event.kind {
             EventKind::Create(_) => true,
             EventKind::Modify(kind) => {
@@ -478,6 +625,55 @@ impl ConfigWatcher {
         }
     }
 
+    /// Wait until two reads `stabilization_delay_ms` apart agree, returning that read.
+    /// Guards against parsing a file an editor is still writing non-atomically.
+    /// انتظار تطابق قراءتين متتاليتين قبل المتابعة لتجنب تحليل ملف قيد الكتابة
+    async fn wait_for_stable_source(
+        file_path: &Path,
+        config: &WatcherConfig,
+    ) -> ConfigWatcherResult<SourceSnapshot> {
+        let mut previous = SourceSnapshot::read(file_path)?;
+        if config.stabilization_delay_ms == 0 {
+            return Ok(previous);
+        }
+        
+        let delay = Duration::from_millis(config.stabilization_delay_ms);
+        for _ in 0..config.stabilization_max_attempts {
+            sleep(delay).await;
+            let current = SourceSnapshot::read(file_path)?;
+            if current == previous {
+                return Ok(current);
+            }
+            debug!("Configuration file still changing, waiting for it to settle");
+            previous = current;
+        }
+        
+        Err(ConfigWatcherError::FileUnstable {
+            path: file_path.to_string_lossy().to_string(),
+            attempts: config.stabilization_max_attempts,
+        })
+    }
+
+    /// Reload after a change notification, skipping it when the settled content
+    /// is what is already loaded (e.g. the trailing event of a multi-step write)
+    /// إعادة التحميل بعد إشعار التغيير مع تخطيها إذا كان المحتوى المستقر محملاً بالفعل
+    async fn reload_if_changed(
+        file_path: &Path,
+        current_config: &Arc<RwLock<AgentConfigurationFile>>,
+        event_sender: &broadcast::Sender<ConfigEvent>,
+        statistics: &Arc<RwLock<WatcherStatistics>>,
+        config: &WatcherConfig,
+        reload_hook: Option<&ReloadHook>,
+    ) -> ConfigWatcherResult<()> {
+        let source = Self::wait_for_stable_source(file_path, config).await?;
+        if statistics.read().await.current_file_checksum.as_deref() == Some(source.checksum().as_str()) {
+            debug!("Configuration content unchanged, skipping reload");
+            return Ok(());
+        }
+        
+        Self::apply_source(source, file_path, current_config, event_sender, statistics, config, reload_hook).await
+    }
+
     /// Reload configuration
     /// إعادة تحميل التكوين
     async fn reload_config(&self) -> ConfigWatcherResult<()> {
@@ -487,6 +683,7 @@ impl ConfigWatcher {
             &self.event_sender,
             &self.statistics,
             &self.config,
+            self.reload_hook.as_ref(),
         ).await
     }
 
@@ -498,6 +695,23 @@ impl ConfigWatcher {
         event_sender: &broadcast::Sender<ConfigEvent>,
         statistics: &Arc<RwLock<WatcherStatistics>>,
         config: &WatcherConfig,
+        reload_hook: Option<&ReloadHook>,
+    ) -> ConfigWatcherResult<()> {
+        // Don't parse a file that is still being written
+        let source = Self::wait_for_stable_source(file_path, config).await?;
+        Self::apply_source(source, file_path, current_config, event_sender, statistics, config, reload_hook).await
+    }
+
+    /// Validate and install configuration from a settled read of the source
+    /// التحقق من التكوين وتثبيته من قراءة مستقرة للمصدر
+    async fn apply_source(
+        source: SourceSnapshot,
+        file_path: &Path,
+        current_config: &Arc<RwLock<AgentConfigurationFile>>,
+        event_sender: &broadcast::Sender<ConfigEvent>,
+        statistics: &Arc<RwLock<WatcherStatistics>>,
+        config: &WatcherConfig,
+        reload_hook: Option<&ReloadHook>,
     ) -> ConfigWatcherResult<()> {
         let start_time = std::time::Instant::now();
         let file_path_str = file_path.to_string_lossy().to_string();
@@ -511,25 +725,25 @@ impl ConfigWatcher {
         }
         
         // Check file size
-        let metadata = std::fs::metadata(file_path)?;
-        if metadata.len() > config.max_file_size_bytes as u64 {
+        let source_size = source.size();
+        if source_size > config.max_file_size_bytes as u64 {
             return Err(ConfigWatcherError::ConfigError(
                 ConfigError::InvalidConfiguration(
                     format!("File size {} exceeds maximum {}", 
-                           metadata.len(), config.max_file_size_bytes)
+                           source_size, config.max_file_size_bytes)
                 )
             ));
         }
         
-        // Create backup if enabled
-        if config.enable_backup {
-            if let Err(e) = Self::create_backup(file_path, config).await {
+        // Create backup if enabled (single-file mode only)
+        if config.enable_backup && !config.watch_directory {
+            if let Err(e) = Self::create_backup(file_path, &source, config).await {
                 warn!("Failed to create backup: {}", e);
             }
         }
         
         // Load new configuration
-        let new_config = match AgentConfigurationFile::load_from_file(&file_path_str) {
+        let mut new_config = match source.parse() {
             Ok(config) => config,
             Err(e) => {
                 // Send reload failed event
@@ -553,10 +767,16 @@ impl ConfigWatcher {
             }
         };
         
-        // Validate new configuration
-        let validation_result = new_config.validate();
+        // Validate new configuration; an invalid file is rejected and the
+        // current configuration stays in place
+        let validation_level = Self::validation_level_for(config, &new_config);
+        let validation_result = new_config.validate_with_level(validation_level.clone());
         if !validation_result.is_valid {
-            warn!("Configuration validation failed: {}", validation_result.summary);
+            warn!(
+                "Configuration validation failed at level '{}', keeping current configuration: {}",
+                validation_level.as_str(),
+                validation_result.summary
+            );
             
             // Send validation failed event
             for agent in &new_config.agents {
@@ -566,7 +786,7 @@ impl ConfigWatcher {
                         config: agent.clone(),
                         errors: validation_result.errors.iter().map(|e| e.message.clone()).collect(),
                         warnings: validation_result.warnings.iter().map(|w| w.message.clone()).collect(),
-                        validation_level: "strict".to_string(),
+                        validation_level: validation_level.as_str().to_string(),
                     },
                     EventSource::FileWatcher,
                 );
@@ -575,6 +795,20 @@ impl ConfigWatcher {
                     error!("Failed to send validation failed event: {}", send_err);
                 }
             }
+            
+            {
+                let mut stats = statistics.write().await;
+                stats.failed_reloads += 1;
+            }
+            
+            return Err(ConfigWatcherError::ConfigError(
+                ConfigError::ValidationError(validation_result.summary)
+            ));
+        }
+        
+        // Adjust before installing so the update events describe what is live
+        if let Some(hook) = reload_hook {
+            hook(&mut new_config);
         }
         
         // Compare with current configuration
@@ -589,9 +823,8 @@ impl ConfigWatcher {
             *config_guard = new_config.clone();
         }
         
-        // Calculate checksum
         let checksum = if config.enable_checksum_verification {
-            Some(Self::calculate_file_checksum_internal(file_path).await?)
+            Some(source.checksum())
         } else {
             None
         };
@@ -612,7 +845,7 @@ impl ConfigWatcher {
                     / total_reloads as f64;
             }
             
-            stats.total_bytes_processed += metadata.len();
+            stats.total_bytes_processed += source_size;
         }
         
         // Send reload completed event
@@ -694,11 +927,12 @@ impl ConfigWatcher {
         // Check for updated agents
         for new_agent in &new_config.agents {
             if let Some(previous_agent) = previous_agents.get(&new_agent.name) {
-                if previous_agent != new_agent {
+                if *previous_agent != new_agent {
                     let payload = super::events::ConfigUpdatePayload {
-                        previous_config: Some(previous_agent.clone()),
+                        previous_config: Some((*previous_agent).clone()),
                         new_config: new_agent.clone(),
                         changed_fields: Self::find_changed_fields(previous_agent, new_agent),
+                        changes: previous_agent.diff(new_agent),
                         reason: "Configuration reload".to_string(),
                         update_source: "file_watcher".to_string(),
                     };
@@ -767,7 +1001,11 @@ impl ConfigWatcher {
 
     /// Create backup of configuration file
     /// إنشاء نسخة احتياطية من ملف التكوين
-    async fn create_backup(file_path: &Path, config: &WatcherConfig) -> ConfigWatcherResult<()> {
+    async fn create_backup(
+        file_path: &Path,
+        source: &SourceSnapshot,
+        config: &WatcherConfig,
+    ) -> ConfigWatcherResult<()> {
         let backup_dir = if let Some(ref dir) = config.backup_directory {
             dir.clone()
         } else {
@@ -782,14 +1020,16 @@ impl ConfigWatcher {
         let file_name = file_path.file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("config");
-        let backup_file_name = format!("{}_{}.toml", file_name, timestamp);
+        let extension = super::config_types::ConfigFormat::from_path(file_path).extension();
+        let backup_file_name = format!("{}_{}.{}", file_name, timestamp, extension);
         let backup_path = backup_dir.join(backup_file_name);
         
-        // Copy file
-        std::fs::copy(file_path, &backup_path)?;
+        // Write the contents that are about to be loaded
+        let contents = source.files.first().map(|(_, content)| content.as_str()).unwrap_or_default();
+        std::fs::write(&backup_path, contents)?;
         
         // Clean up old backups
-        Self::cleanup_old_backups(&backup_dir, file_name, config.backup_count).await?;
+        Self::cleanup_old_backups(&backup_dir, file_name, extension, config.backup_count).await?;
         
         info!("Configuration backup created: {:?}", backup_path);
         Ok(())
@@ -800,6 +1040,7 @@ impl ConfigWatcher {
     async fn cleanup_old_backups(
         backup_dir: &Path,
         file_prefix: &str,
+        extension: &str,
         keep_count: usize,
     ) -> ConfigWatcherResult<()> {
         let mut backup_files = Vec::new();
@@ -810,7 +1051,7 @@ impl ConfigWatcher {
             let path = entry.path();
             
             if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-                if file_name.starts_with(file_prefix) && file_name.ends_with(".toml") {
+                if file_name.starts_with(file_prefix) && file_name.ends_with(&format!(".{}", extension)) {
                     if let Ok(metadata) = entry.metadata() {
                         if let Ok(modified) = metadata.modified() {
                             backup_files.push((path, modified));
@@ -839,26 +1080,10 @@ impl ConfigWatcher {
         Self::calculate_file_checksum_internal(&self.file_path).await
     }
 
-    /// Internal checksum calculation
-    /// حساب المجموع الاختباري الداخلي
+    /// Internal checksum calculation; a directory hashes every fragment's path and contents
+    /// حساب المجموع الاختباري الداخلي؛ الدليل يجمع مسار ومحتوى كل جزء
     async fn calculate_file_checksum_internal(file_path: &Path) -> ConfigWatcherResult<String> {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        use std::io::Read;
-        
-        let mut file = std::fs::File::open(file_path)?;
-        let mut hasher = DefaultHasher::new();
-        let mut buffer = [0; 8192];
-        
-        loop {
-            let bytes_read = file.read(&mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            hasher.write(&buffer[..bytes_read]);
-        }
-        
-        Ok(format!("{:x}", hasher.finish()))
+        Ok(SourceSnapshot::read(file_path)?.checksum())
     }
 }
 
@@ -1019,6 +1244,187 @@ interval_ms = 500
         assert!(stats.last_reload_timestamp.is_some());
     }
 
+    fn fragment(global: bool, agents: &[&str]) -> String {
+        let mut content = String::new();
+        if global {
+            content.push_str(
+                r#"
+[global]
+default_interval_ms = 1000
+max_concurrent_agents = 10
+operation_timeout_seconds = 30
+enable_hot_reload = true
+validation_level = "basic"
+"#,
+            );
+        }
+        for name in agents {
+            content.push_str(&format!(
+                r#"
+[[agents]]
+name = "{}"
+enabled = true
+interval_ms = 500
+description = "Fragment agent"
+version = "1.0.0"
+author = "Test Author"
+
+[agents.risk]
+max_drawdown = 0.05
+max_position_value = 50000
+leverage_limit = 2.0
+
+[agents.monitoring]
+enable_metrics = true
+log_level = "info"
+performance_tracking = true
+"#,
+                name
+            ));
+        }
+        content
+    }
+
+    fn directory_config() -> WatcherConfig {
+        WatcherConfig {
+            enable_file_watching: false,
+            watch_directory: true,
+            ..WatcherConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_directory_fragments_are_merged() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("00-global.toml"), fragment(true, &["AlphaAgent"])).unwrap();
+        std::fs::create_dir(dir.path().join("desk")).unwrap();
+        std::fs::write(dir.path().join("desk/beta.toml"), fragment(false, &["BetaAgent"])).unwrap();
+        std::fs::write(dir.path().join("README.md"), "not a fragment").unwrap();
+
+        let (watcher, _receiver) = ConfigWatcher::new(dir.path(), directory_config()).unwrap();
+
+        let config = watcher.get_config().await;
+        let names: Vec<_> = config.agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["AlphaAgent", "BetaAgent"]);
+        assert_eq!(config.global.max_concurrent_agents, 10);
+    }
+
+    #[tokio::test]
+    async fn test_directory_reload_picks_up_new_fragment() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.toml"), fragment(true, &["AlphaAgent"])).unwrap();
+        std::fs::write(dir.path().join("b.toml"), fragment(false, &["BetaAgent"])).unwrap();
+
+        let (watcher, mut receiver) = ConfigWatcher::new(dir.path(), directory_config()).unwrap();
+        let checksum_before = ConfigWatcher::calculate_file_checksum_internal(dir.path()).await.unwrap();
+
+        std::fs::write(dir.path().join("c.toml"), fragment(false, &["GammaAgent"])).unwrap();
+        let checksum_after = ConfigWatcher::calculate_file_checksum_internal(dir.path()).await.unwrap();
+        assert_ne!(checksum_before, checksum_after);
+
+        watcher.force_reload().await.unwrap();
+        assert_eq!(watcher.get_config().await.agents.len(), 3);
+
+        let mut added = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            if matches!(event.event_type, super::super::events::ConfigEventType::AgentAdded) {
+                added.push(event.agent_name.clone());
+            }
+        }
+        assert_eq!(added, vec![Some("GammaAgent".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_directory_duplicate_agent_is_a_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.toml"), fragment(true, &["AlphaAgent"])).unwrap();
+        std::fs::write(dir.path().join("b.toml"), fragment(false, &["BetaAgent"])).unwrap();
+
+        let (watcher, _receiver) = ConfigWatcher::new(dir.path(), directory_config()).unwrap();
+        std::fs::write(dir.path().join("c.toml"), fragment(false, &["AlphaAgent"])).unwrap();
+
+        match AgentConfigurationFile::load_from_directory(dir.path()) {
+            Err(ConfigError::DuplicateAgentName(message)) => {
+                assert!(message.contains("AlphaAgent"));
+                assert!(message.contains("a.toml") && message.contains("c.toml"));
+            }
+            other => panic!("expected a duplicate agent conflict, got {:?}", other),
+        }
+
+        // The conflicting reload is rejected and the merged config stays in place
+        assert!(watcher.force_reload().await.is_err());
+        assert_eq!(watcher.get_config().await.agents.len(), 2);
+        assert_eq!(watcher.get_statistics().await.failed_reloads, 1);
+    }
+
+    #[tokio::test]
+    async fn test_torn_write_is_not_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agents.toml");
+        std::fs::write(&path, fragment(true, &["AlphaAgent"])).unwrap();
+
+        let config = WatcherConfig {
+            enable_file_watching: false,
+            enable_backup: false,
+            stabilization_delay_ms: 100,
+            ..WatcherConfig::default()
+        };
+        let (watcher, _receiver) = ConfigWatcher::new(&path, config).unwrap();
+        watcher.load_initial_config().await.unwrap();
+
+        // First half of a non-atomic write lands, the rest follows shortly after
+        let complete = fragment(true, &["AlphaAgent", "BetaAgent"]);
+        std::fs::write(&path, &complete[..complete.len() / 2]).unwrap();
+        let writer_path = path.clone();
+        let writer = tokio::spawn(async move {
+            sleep(Duration::from_millis(30)).await;
+            std::fs::write(writer_path, complete).unwrap();
+        });
+
+        // One notification per write
+        let on_change = || ConfigWatcher::reload_if_changed(
+            &watcher.file_path,
+            &watcher.current_config,
+            &watcher.event_sender,
+            &watcher.statistics,
+            &watcher.config,
+            None,
+        );
+        on_change().await.unwrap();
+        writer.await.unwrap();
+        on_change().await.unwrap();
+
+        let stats = watcher.get_statistics().await;
+        assert_eq!(stats.successful_reloads, 1);
+        assert_eq!(stats.failed_reloads, 0);
+        assert_eq!(watcher.get_config().await.agents.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_never_settling_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agents.toml");
+        std::fs::write(&path, fragment(true, &["AlphaAgent"])).unwrap();
+
+        let config = WatcherConfig {
+            stabilization_delay_ms: 20,
+            stabilization_max_attempts: 3,
+            ..WatcherConfig::default()
+        };
+
+        let writer_path = path.clone();
+        let writer = tokio::spawn(async move {
+            for i in 0..20 {
+                std::fs::write(&writer_path, format!("# partial write {}", i)).unwrap();
+                sleep(Duration::from_millis(5)).await;
+            }
+        });
+
+        let result = ConfigWatcher::wait_for_stable_source(&path, &config).await;
+        assert!(matches!(result, Err(ConfigWatcherError::FileUnstable { attempts: 3, .. })));
+        writer.await.unwrap();
+    }
+
     #[test]
     fn test_watcher_config() {
         let config = WatcherConfig::default();
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use super::config_types::{AgentConfig, ConfigError};
+use super::config_types::{AgentConfig, ConfigError, FieldChange};
 
 /// Configuration event types
 /// أنواع أحداث التكوين
@@ -181,6 +181,11 @@ pub struct ConfigUpdatePayload {
     /// الحقول المتغيرة
     pub changed_fields: Vec<String>,
     
+    /// Before/after values of each changed field
+    /// القيم قبل وبعد لكل حقل متغير
+    #[serde(default)]
+    pub changes: Vec<FieldChange>,
+    
     /// Update reason
     /// سبب التحديث
     pub reason: String,
@@ -2,12 +2,132 @@
 //!
 //! Stub implementation – replace with real logic as needed.
 
+use chrono::{DateTime, Utc};
+use opentelemetry::trace::TraceContextExt;
+use serde::Serialize;
+use std::collections::HashMap;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Event types, mirroring `analytics.EventType` in analytics.proto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AnalyticsEventType {
+    MarketDataReceived,
+    MarketDataProcessed,
+    OrderExecuted,
+    OrderFailed,
+    CacheHit,
+    CacheMiss,
+    RateLimitTriggered,
+    CircuitBreakerOpened,
+    CircuitBreakerClosed,
+    ServiceStarted,
+    ServiceStopped,
+    HealthCheck,
+    Custom,
+}
+
+/// A single analytics event
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsEvent {
+    pub id: String,
+    pub event_type: AnalyticsEventType,
+    pub timestamp: DateTime<Utc>,
+    pub service: String,
+    pub fields: HashMap<String, serde_json::Value>,
+    /// Trace the event was created under, so it can be joined with spans
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
+}
+
+impl AnalyticsEvent {
+    /// New event stamped with the trace and span ids of the current
+    /// `tracing` span, when it is exported through OpenTelemetry
+    pub fn new(event_type: AnalyticsEventType) -> Self {
+        let (trace_id, span_id) = current_trace_ids().unzip();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            timestamp: Utc::now(),
+            service: "core-engine".to_string(),
+            fields: HashMap::new(),
+            trace_id,
+            span_id,
+        }
+    }
+
+    pub fn with_field(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+fn current_trace_ids() -> Option<(String, String)> {
+    let cx = tracing::Span::current().context();
+    let span = cx.span();
+    let span_context = span.span_context();
+    span_context
+        .is_valid()
+        .then(|| (span_context.trace_id().to_string(), span_context.span_id().to_string()))
+}
+
 /// Initialise the analytics subsystem.
 pub fn init() {
     tracing::info!("Analytics subsystem initialised");
 }
 
+/// Record an event. Until a publisher is wired in, events are written to
+/// the `analytics` log target.
+pub fn emit(event: AnalyticsEvent) {
+    tracing::info!(
+        target: "analytics",
+        event_type = ?event.event_type,
+        event_id = %event.id,
+        trace_id = event.trace_id.as_deref().unwrap_or_default(),
+        fields = %serde_json::to_string(&event.fields).unwrap_or_default(),
+        "analytics event"
+    );
+}
+
 /// Clean up the analytics subsystem on shutdown.
 pub fn cleanup() {
     tracing::info!("Analytics subsystem cleaned up");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_event_carries_active_trace_id() {
+        let provider = TracerProvider::builder().build();
+        let tracer = provider.tracer("analytics-test");
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("process_message");
+            let _entered = span.enter();
+
+            let event = AnalyticsEvent::new(AnalyticsEventType::MarketDataProcessed);
+            let cx = span.context();
+            let expected = cx.span().span_context().clone();
+
+            assert!(expected.is_valid());
+            assert_eq!(event.trace_id, Some(expected.trace_id().to_string()));
+            assert_eq!(event.span_id, Some(expected.span_id().to_string()));
+        });
+    }
+
+    #[test]
+    fn test_event_without_span_has_no_trace_id() {
+        let event = AnalyticsEvent::new(AnalyticsEventType::HealthCheck);
+        assert!(event.trace_id.is_none());
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert!(json.get("trace_id").is_none());
+    }
+}
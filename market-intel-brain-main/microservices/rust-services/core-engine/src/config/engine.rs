@@ -0,0 +1,397 @@
+//! Core Engine service configuration
+//!
+//! Settings come from three layers, later ones winning: built-in defaults,
+//! an optional TOML file (see `config/app.toml`), then environment variables.
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::Path;
+use tracing::warn;
+
+use crate::errors::{EngineError, EngineResult};
+use crate::middleware::DEFAULT_REQUEST_TIMEOUT;
+
+/// Top-level Core Engine configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoreEngineConfig {
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub vector_store: VectorStoreConfig,
+    #[serde(default)]
+    pub data_ingestion: DataIngestionConfig,
+    #[serde(default = "default_environment")]
+    pub environment: String,
+}
+
+/// gRPC / HTTP listener settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub host: String,
+    #[serde(alias = "port")]
+    pub grpc_port: u16,
+    pub http_port: u16,
+    pub workers: usize,
+    pub max_connections: usize,
+    /// TCP keep-alive in seconds
+    pub keep_alive: u64,
+    /// Deadline for unary RPCs in milliseconds
+    pub request_timeout_ms: u64,
+    /// Concurrent requests admitted before new ones are shed
+    pub max_in_flight: usize,
+}
+
+/// Log output settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub level: String,
+    pub format: String,
+    /// Level of the per-request gRPC access log
+    pub request_level: String,
+    /// Refuse to start if the trace exporter cannot be initialized
+    pub telemetry_required: bool,
+}
+
+/// Prometheus exporter settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub endpoint: String,
+}
+
+/// Vector store (Qdrant) settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VectorStoreConfig {
+    /// Initialize the vector store and require it for readiness
+    pub enabled: bool,
+}
+
+/// Data ingestion settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataIngestionConfig {
+    /// Sources registered at startup; the engine isn't ready until one is
+    pub sources: Vec<String>,
+}
+
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+
+fn default_environment() -> String {
+    "development".to_string()
+}
+
+impl Default for CoreEngineConfig {
+    fn default() -> Self {
+        Self {
+            server: ServerConfig::default(),
+            logging: LoggingConfig::default(),
+            metrics: MetricsConfig::default(),
+            vector_store: VectorStoreConfig::default(),
+            data_ingestion: DataIngestionConfig::default(),
+            environment: default_environment(),
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            grpc_port: 50052,
+            http_port: 8081,
+            workers: 4,
+            max_connections: 1000,
+            keep_alive: 30,
+            request_timeout_ms: DEFAULT_REQUEST_TIMEOUT.as_millis() as u64,
+            max_in_flight: 1000,
+        }
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: "json".to_string(),
+            request_level: "info".to_string(),
+            telemetry_required: false,
+        }
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            port: 9091,
+            endpoint: "/metrics".to_string(),
+        }
+    }
+}
+
+impl Default for VectorStoreConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl CoreEngineConfig {
+    /// Defaults overlaid with environment variables.
+    pub fn from_env() -> EngineResult<Self> {
+        let mut config = Self::default();
+        config.apply_env(|key| env::var(key).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Read a TOML file; sections and keys it omits keep their defaults.
+    pub fn from_file(path: impl AsRef<Path>) -> EngineResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| EngineError::Config(format!("cannot read {}: {}", path.display(), e)))?;
+        let config: Self = toml::from_str(&content)
+            .map_err(|e| EngineError::Config(format!("cannot parse {}: {}", path.display(), e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Layered load: defaults, then `path` if it exists, then environment.
+    pub fn load(path: impl AsRef<Path>) -> EngineResult<Self> {
+        Self::load_with(path.as_ref(), |key| env::var(key).ok())
+    }
+
+    fn load_with<F>(path: &Path, lookup: F) -> EngineResult<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut config = if path.exists() {
+            Self::from_file(path)?
+        } else {
+            warn!("Config file {} not found, using defaults", path.display());
+            Self::default()
+        };
+        config.apply_env(lookup)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Overlay the environment variables used by the deployment manifests.
+    ///
+    /// | Variable               | Field                        |
+    /// |------------------------|------------------------------|
+    /// | `HOST`                 | `server.host`                |
+    /// | `GRPC_PORT`            | `server.grpc_port`           |
+    /// | `HTTP_PORT`            | `server.http_port`           |
+    /// | `NUM_WORKERS`          | `server.workers`             |
+    /// | `REQUEST_TIMEOUT_MS`   | `server.request_timeout_ms`  |
+    /// | `MAX_IN_FLIGHT`        | `server.max_in_flight`       |
+    /// | `LOG_LEVEL`            | `logging.level`              |
+    /// | `REQUEST_LOG_LEVEL`    | `logging.request_level`      |
+    /// | `LOG_FORMAT`           | `logging.format`             |
+    /// | `TELEMETRY_REQUIRED`   | `logging.telemetry_required` |
+    /// | `METRICS_ENABLED`      | `metrics.enabled`            |
+    /// | `METRICS_PORT`         | `metrics.port`               |
+    /// | `VECTOR_STORE_ENABLED` | `vector_store.enabled`       |
+    /// | `DATA_SOURCES`         | `data_ingestion.sources`     |
+    /// | `ENVIRONMENT`          | `environment`                |
+    fn apply_env<F>(&mut self, lookup: F) -> EngineResult<()>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(v) = lookup("HOST") {
+            self.server.host = v;
+        }
+        if let Some(v) = lookup("GRPC_PORT") {
+            self.server.grpc_port = parse_env("GRPC_PORT", &v)?;
+        }
+        if let Some(v) = lookup("HTTP_PORT") {
+            self.server.http_port = parse_env("HTTP_PORT", &v)?;
+        }
+        if let Some(v) = lookup("NUM_WORKERS") {
+            self.server.workers = parse_env("NUM_WORKERS", &v)?;
+        }
+        if let Some(v) = lookup("REQUEST_TIMEOUT_MS") {
+            self.server.request_timeout_ms = parse_env("REQUEST_TIMEOUT_MS", &v)?;
+        }
+        if let Some(v) = lookup("MAX_IN_FLIGHT") {
+            self.server.max_in_flight = parse_env("MAX_IN_FLIGHT", &v)?;
+        }
+        if let Some(v) = lookup("LOG_LEVEL") {
+            self.logging.level = v.to_lowercase();
+        }
+        if let Some(v) = lookup("REQUEST_LOG_LEVEL") {
+            self.logging.request_level = v.to_lowercase();
+        }
+        if let Some(v) = lookup("LOG_FORMAT") {
+            self.logging.format = v;
+        }
+        if let Some(v) = lookup("TELEMETRY_REQUIRED") {
+            self.logging.telemetry_required = parse_env("TELEMETRY_REQUIRED", &v)?;
+        }
+        if let Some(v) = lookup("METRICS_ENABLED") {
+            self.metrics.enabled = parse_env("METRICS_ENABLED", &v)?;
+        }
+        if let Some(v) = lookup("METRICS_PORT") {
+            self.metrics.port = parse_env("METRICS_PORT", &v)?;
+        }
+        if let Some(v) = lookup("VECTOR_STORE_ENABLED") {
+            self.vector_store.enabled = parse_env("VECTOR_STORE_ENABLED", &v)?;
+        }
+        if let Some(v) = lookup("DATA_SOURCES") {
+            // Comma-separated, e.g. "yahoo_finance,alpha_vantage"
+            self.data_ingestion.sources = v
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(v) = lookup("ENVIRONMENT") {
+            self.environment = v;
+        }
+        Ok(())
+    }
+
+    /// Reject settings the server cannot start with.
+    pub fn validate(&self) -> EngineResult<()> {
+        if self.server.grpc_port == 0 {
+            return Err(EngineError::Config(
+                "server.grpc_port cannot be 0".to_string(),
+            ));
+        }
+        if self.server.request_timeout_ms == 0 {
+            return Err(EngineError::Config(
+                "server.request_timeout_ms cannot be 0".to_string(),
+            ));
+        }
+        if self.server.max_in_flight == 0 {
+            return Err(EngineError::Config(
+                "server.max_in_flight cannot be 0".to_string(),
+            ));
+        }
+        if self.server.workers == 0 {
+            return Err(EngineError::Config(
+                "server.workers cannot be 0".to_string(),
+            ));
+        }
+        if self.metrics.enabled && self.metrics.port == self.server.grpc_port {
+            return Err(EngineError::Config(format!(
+                "metrics.port and server.grpc_port both use {}",
+                self.metrics.port
+            )));
+        }
+        if !LOG_LEVELS.contains(&self.logging.level.as_str()) {
+            return Err(EngineError::Config(format!(
+                "logging.level '{}' is not one of {:?}",
+                self.logging.level, LOG_LEVELS
+            )));
+        }
+        if !LOG_LEVELS.contains(&self.logging.request_level.as_str()) {
+            return Err(EngineError::Config(format!(
+                "logging.request_level '{}' is not one of {:?}",
+                self.logging.request_level, LOG_LEVELS
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(key: &str, value: &str) -> EngineResult<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| EngineError::Config(format!("{} has invalid value '{}'", key, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn write_temp(contents: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        std::fs::write(file.path(), contents).unwrap();
+        file
+    }
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| map.get(key).cloned()
+    }
+
+    #[test]
+    fn test_shipped_app_toml_loads() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("config/app.toml");
+        let config = CoreEngineConfig::from_file(path).unwrap();
+        assert_eq!(config.server.grpc_port, 50052);
+        assert_eq!(config.metrics.port, 9091);
+    }
+
+    #[test]
+    fn test_env_overrides_file_value() {
+        let file = write_temp("[server]\nport = 6000\n\n[logging]\nlevel = \"warn\"\n");
+        let config = CoreEngineConfig::load_with(file.path(), env_of(&[("GRPC_PORT", "7000")])).unwrap();
+
+        assert_eq!(config.server.grpc_port, 7000);
+        assert_eq!(config.logging.level, "warn");
+        assert_eq!(config.server.host, "0.0.0.0");
+    }
+
+    #[test]
+    fn test_missing_file_falls_back_to_defaults() {
+        let config =
+            CoreEngineConfig::load_with(Path::new("/nonexistent/core-engine.toml"), env_of(&[]))
+                .unwrap();
+        assert_eq!(config, CoreEngineConfig::default());
+    }
+
+    #[test]
+    fn test_vector_store_can_be_disabled() {
+        assert!(CoreEngineConfig::default().vector_store.enabled);
+
+        let file = write_temp("[vector_store]\nenabled = false\n");
+        let config = CoreEngineConfig::load_with(file.path(), env_of(&[])).unwrap();
+        assert!(!config.vector_store.enabled);
+
+        let config = CoreEngineConfig::load_with(file.path(), env_of(&[("VECTOR_STORE_ENABLED", "true")])).unwrap();
+        assert!(config.vector_store.enabled);
+    }
+
+    #[test]
+    fn test_data_sources_from_file_and_env() {
+        let file = write_temp("[data_ingestion]\nsources = [\"yahoo_finance\"]\n");
+        let config = CoreEngineConfig::load_with(file.path(), env_of(&[])).unwrap();
+        assert_eq!(config.data_ingestion.sources, vec!["yahoo_finance"]);
+
+        let config =
+            CoreEngineConfig::load_with(file.path(), env_of(&[("DATA_SOURCES", "alpha_vantage, polygon,")])).unwrap();
+        assert_eq!(config.data_ingestion.sources, vec!["alpha_vantage", "polygon"]);
+    }
+
+    #[test]
+    fn test_invalid_merged_config_is_rejected() {
+        let result = CoreEngineConfig::load_with(
+            Path::new("/nonexistent/core-engine.toml"),
+            env_of(&[("GRPC_PORT", "9091")]),
+        );
+        assert!(matches!(result, Err(EngineError::Config(_))));
+
+        let result = CoreEngineConfig::load_with(
+            Path::new("/nonexistent/core-engine.toml"),
+            env_of(&[("GRPC_PORT", "not-a-port")]),
+        );
+        assert!(matches!(result, Err(EngineError::Config(_))));
+    }
+}
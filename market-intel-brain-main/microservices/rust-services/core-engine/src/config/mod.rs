@@ -1,4 +1,8 @@
+pub mod engine;
+pub mod reload;
 pub mod tracing;
 pub mod kafka;
 
+pub use engine::CoreEngineConfig;
+pub use reload::{ConfigReloader, RuntimeTunables};
 pub use tracing::TracingConfig;
@@ -0,0 +1,231 @@
+//! Live reload of runtime tunables
+//!
+//! Only the log level is applied while the service runs. Edits to any other
+//! field (ports, worker counts, ...) are logged and take effect on the next
+//! restart.
+
+use arc_swap::ArcSwap;
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use super::engine::CoreEngineConfig;
+use crate::errors::EngineResult;
+
+/// The subset of `CoreEngineConfig` that can change without a restart
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeTunables {
+    pub log_level: String,
+}
+
+impl RuntimeTunables {
+    pub fn from_config(config: &CoreEngineConfig) -> Self {
+        Self {
+            log_level: config.logging.level.clone(),
+        }
+    }
+}
+
+type LogLevelHook = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Watches the config file and swaps in new [`RuntimeTunables`] on change
+pub struct ConfigReloader {
+    path: PathBuf,
+    startup: CoreEngineConfig,
+    tunables: Arc<ArcSwap<RuntimeTunables>>,
+    log_level_hook: Option<LogLevelHook>,
+    last_seen: Mutex<Option<(SystemTime, u64)>>,
+}
+
+impl ConfigReloader {
+    /// `startup` is the config the service was started with; it is the
+    /// reference for detecting edits to fields that need a restart.
+    pub fn new(path: impl AsRef<Path>, startup: CoreEngineConfig) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let last_seen = Mutex::new(file_stamp(&path));
+        Self {
+            tunables: Arc::new(ArcSwap::from_pointee(RuntimeTunables::from_config(
+                &startup,
+            ))),
+            path,
+            startup,
+            log_level_hook: None,
+            last_seen,
+        }
+    }
+
+    /// Called with the new level whenever `logging.level` changes.
+    pub fn with_log_level_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.log_level_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Current tunables.
+    pub fn tunables(&self) -> Arc<RuntimeTunables> {
+        self.tunables.load_full()
+    }
+
+    /// Shared handle for components that read tunables on their hot path.
+    pub fn handle(&self) -> Arc<ArcSwap<RuntimeTunables>> {
+        self.tunables.clone()
+    }
+
+    /// Re-read the file and apply any tunable changes.
+    ///
+    /// Returns `Ok(true)` if the tunables changed. An invalid file leaves the
+    /// current tunables in place.
+    pub fn reload(&self) -> EngineResult<bool> {
+        *self.last_seen.lock() = file_stamp(&self.path);
+        let config = CoreEngineConfig::load(&self.path)?;
+        self.warn_on_restart_only_changes(&config);
+
+        let next = RuntimeTunables::from_config(&config);
+        let current = self.tunables.load();
+        if **current == next {
+            return Ok(false);
+        }
+
+        if current.log_level != next.log_level {
+            if let Some(hook) = &self.log_level_hook {
+                if let Err(e) = hook(&next.log_level) {
+                    warn!("Failed to apply log level '{}': {}", next.log_level, e);
+                }
+            }
+        }
+        info!("Reloaded runtime config: log_level={}", next.log_level);
+        self.tunables.store(Arc::new(next));
+        Ok(true)
+    }
+
+    /// Poll the file for changes every `interval`.
+    ///
+    /// The task holds only a weak reference and stops once the reloader is
+    /// dropped.
+    pub fn start(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let reloader: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Some(this) = reloader.upgrade() else {
+                    break;
+                };
+                if !this.file_changed() {
+                    continue;
+                }
+                debug!("Config file {} changed", this.path.display());
+                if let Err(e) = this.reload() {
+                    error!("Config reload failed, keeping previous values: {}", e);
+                }
+            }
+        })
+    }
+
+    fn file_changed(&self) -> bool {
+        file_stamp(&self.path) != *self.last_seen.lock()
+    }
+
+    fn warn_on_restart_only_changes(&self, config: &CoreEngineConfig) {
+        if config.server != self.startup.server {
+            warn!("Ignoring [server] changes until restart");
+        }
+        if config.metrics != self.startup.metrics {
+            warn!("Ignoring [metrics] changes until restart");
+        }
+        if config.logging.format != self.startup.logging.format {
+            warn!("Ignoring logging.format change until restart");
+        }
+        if config.environment != self.startup.environment {
+            warn!("Ignoring environment change until restart");
+        }
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config(contents: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        std::fs::write(file.path(), contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_editing_file_changes_log_level() {
+        let file = temp_config("[logging]\nlevel = \"info\"\n");
+        let levels = Arc::new(Mutex::new(Vec::new()));
+        let seen = levels.clone();
+        let reloader = ConfigReloader::new(file.path(), CoreEngineConfig::from_file(file.path()).unwrap())
+            .with_log_level_hook(move |level| {
+                seen.lock().push(level.to_string());
+                Ok(())
+            });
+        assert_eq!(reloader.tunables().log_level, "info");
+
+        std::fs::write(file.path(), "[logging]\nlevel = \"debug\"\n").unwrap();
+        assert!(reloader.reload().unwrap());
+
+        assert_eq!(reloader.tunables().log_level, "debug");
+        assert_eq!(*levels.lock(), vec!["debug".to_string()]);
+    }
+
+    #[test]
+    fn test_port_change_is_ignored() {
+        let file = temp_config("[server]\nport = 6000\n");
+        let reloader = ConfigReloader::new(file.path(), CoreEngineConfig::from_file(file.path()).unwrap());
+        let before = reloader.tunables();
+
+        std::fs::write(file.path(), "[server]\nport = 6001\n").unwrap();
+        assert!(!reloader.reload().unwrap());
+
+        assert_eq!(*reloader.tunables(), *before);
+    }
+
+    #[test]
+    fn test_invalid_file_keeps_previous_tunables() {
+        let file = temp_config("[logging]\nlevel = \"warn\"\n");
+        let reloader = ConfigReloader::new(file.path(), CoreEngineConfig::from_file(file.path()).unwrap());
+
+        std::fs::write(file.path(), "[logging]\nlevel = \"loud\"\n").unwrap();
+        assert!(reloader.reload().is_err());
+
+        assert_eq!(reloader.tunables().log_level, "warn");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_picks_up_edit() {
+        let file = temp_config("[logging]\nlevel = \"info\"\n");
+        let reloader = Arc::new(ConfigReloader::new(
+            file.path(),
+            CoreEngineConfig::from_file(file.path()).unwrap(),
+        ));
+        let task = reloader.start(Duration::from_millis(20));
+
+        std::fs::write(file.path(), "[logging]\nlevel = \"trace\"\n").unwrap();
+        for _ in 0..100 {
+            if reloader.tunables().log_level == "trace" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(reloader.tunables().log_level, "trace");
+
+        drop(reloader);
+        tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .expect("reload task should stop once the reloader is dropped")
+            .unwrap();
+    }
+}
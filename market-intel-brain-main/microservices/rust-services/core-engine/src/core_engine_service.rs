@@ -1,7 +1,11 @@
 use tonic::{Request, Response, Status};
 use tracing::{error, info, warn};
 
+use std::sync::Arc;
+
 use crate::config::CoreEngineConfig;
+use crate::data_ingestion::DataIngestionService;
+use crate::errors::EngineResult;
 
 // ── generated types (تولّدها tonic-build من proto) ──────────────────────────
 // استخدم include! لاستيراد الكود المولّد مباشرة
@@ -22,14 +26,15 @@ use proto::common::*;
 
 pub struct CoreEngineServiceImpl {
     config: CoreEngineConfig,
+    ingestion: Arc<DataIngestionService>,
 }
 
 impl CoreEngineServiceImpl {
-    pub async fn new(config: CoreEngineConfig) 
-        -> Result<Self, Box<dyn std::error::Error>> 
-    {
+    /// `ingestion` is shared with the readiness probe, so sources registered
+    /// through it count towards readiness
+    pub async fn new(config: CoreEngineConfig, ingestion: Arc<DataIngestionService>) -> EngineResult<Self> {
         info!("Initializing CoreEngineServiceImpl");
-        Ok(Self { config })
+        Ok(Self { config, ingestion })
     }
 
     /// Convert into a tonic service ready for Server::add_service()
@@ -44,10 +49,11 @@ impl CoreEngineService for CoreEngineServiceImpl {
         &self,
         _request: Request<HealthCheckRequest>,
     ) -> Result<Response<HealthCheckResponse>, Status> {
+        let sources = self.ingestion.get_sources().await;
         Ok(Response::new(HealthCheckResponse {
             status: health_check_response::ServingStatus::Serving as i32,
             message: "healthy".to_string(),
-            details: std::collections::HashMap::new(),
+            details: std::collections::HashMap::from([("data_sources".to_string(), sources.len().to_string())]),
         }))
     }
 
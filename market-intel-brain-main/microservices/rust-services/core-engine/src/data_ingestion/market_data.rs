@@ -0,0 +1,193 @@
+// Copyright (c) 2024 Market Intel Brain Team
+// Market Data Domain Type
+// نوع مجال بيانات السوق
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use super::service::DataIngestionError;
+use super::symbol::Symbol;
+use crate::core_engine_service::proto::common::MarketDataPoint;
+
+/// Internal market data tick, independent of the wire format
+/// نقطة بيانات السوق الداخلية، مستقلة عن تنسيق النقل
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketData {
+    pub symbol: Symbol,
+    pub price: f64,
+    pub volume: f64,
+    pub timestamp: DateTime<Utc>,
+    pub metadata: HashMap<String, String>,
+}
+
+impl From<MarketData> for MarketDataPoint {
+    fn from(data: MarketData) -> Self {
+        Self {
+            symbol: data.symbol.into(),
+            price: data.price,
+            volume: data.volume,
+            timestamp: Some(to_proto_timestamp(&data.timestamp)),
+            metadata: data
+                .metadata
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = prost_types::Value {
+                        kind: Some(prost_types::value::Kind::StringValue(value)),
+                    };
+                    (key, value)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<MarketDataPoint> for MarketData {
+    type Error = DataIngestionError;
+
+    fn try_from(point: MarketDataPoint) -> Result<Self, Self::Error> {
+        let symbol = Symbol::parse(&point.symbol)?;
+        let timestamp = point
+            .timestamp
+            .as_ref()
+            .ok_or_else(|| {
+                DataIngestionError::InvalidData(format!("{}: missing timestamp", point.symbol))
+            })
+            .and_then(|ts| from_proto_timestamp(ts))?;
+
+        let metadata = point
+            .metadata
+            .into_iter()
+            .filter_map(|(key, value)| match value.kind {
+                Some(prost_types::value::Kind::StringValue(s)) => Some((key, s)),
+                Some(prost_types::value::Kind::NumberValue(n)) => Some((key, n.to_string())),
+                Some(prost_types::value::Kind::BoolValue(b)) => Some((key, b.to_string())),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Self {
+            symbol,
+            price: point.price,
+            volume: point.volume,
+            timestamp,
+            metadata,
+        })
+    }
+}
+
+/// Convert to a protobuf timestamp without going through nanoseconds
+/// التحويل إلى طابع زمني protobuf دون المرور بالنانو ثانية
+pub fn to_proto_timestamp(ts: &DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: ts.timestamp(),
+        nanos: ts.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// Convert from a protobuf timestamp, rejecting out-of-range values
+/// التحويل من طابع زمني protobuf مع رفض القيم خارج النطاق
+pub fn from_proto_timestamp(
+    ts: &prost_types::Timestamp,
+) -> Result<DateTime<Utc>, DataIngestionError> {
+    u32::try_from(ts.nanos)
+        .ok()
+        .and_then(|nanos| Utc.timestamp_opt(ts.seconds, nanos).single())
+        .ok_or_else(|| {
+            DataIngestionError::InvalidData(format!(
+                "timestamp out of range: {}s {}ns",
+                ts.seconds, ts.nanos
+            ))
+        })
+}
+
+/// Nanoseconds since the epoch, saturating instead of panicking
+/// النانو ثانية منذ الحقبة، مع التشبع بدلاً من الذعر
+///
+/// `DateTime::timestamp_nanos` panics outside roughly 1677-2262; this
+/// clamps to `i64::MIN`/`i64::MAX` instead.
+pub fn timestamp_nanos_saturating(ts: &DateTime<Utc>) -> i64 {
+    ts.timestamp_nanos_opt().unwrap_or_else(|| {
+        ts.timestamp()
+            .saturating_mul(1_000_000_000)
+            .saturating_add(i64::from(ts.timestamp_subsec_nanos()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MarketData {
+        MarketData {
+            symbol: Symbol::parse("AAPL").unwrap(),
+            price: 189.25,
+            volume: 1_200_000.0,
+            timestamp: Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap(),
+            metadata: HashMap::from([("exchange".to_string(), "NASDAQ".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_proto() {
+        let data = sample();
+        let point = MarketDataPoint::from(data.clone());
+        assert_eq!(point.timestamp.as_ref().unwrap().nanos, 123_456_789);
+
+        let back = MarketData::try_from(point).unwrap();
+        assert_eq!(back, data);
+    }
+
+    #[test]
+    fn test_missing_timestamp_is_rejected() {
+        let mut point = MarketDataPoint::from(sample());
+        point.timestamp = None;
+        assert!(matches!(
+            MarketData::try_from(point),
+            Err(DataIngestionError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_symbol_is_rejected() {
+        let mut point = MarketDataPoint::from(sample());
+        point.symbol = "aapl".to_string();
+        assert!(matches!(
+            MarketData::try_from(point),
+            Err(DataIngestionError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_nanos_in_range() {
+        let ts = Utc.timestamp_opt(1_700_000_000, 5).unwrap();
+        assert_eq!(timestamp_nanos_saturating(&ts), 1_700_000_000_000_000_005);
+    }
+
+    #[test]
+    fn test_timestamp_nanos_far_future_saturates() {
+        let far_future = Utc.with_ymd_and_hms(3000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(timestamp_nanos_saturating(&far_future), i64::MAX);
+
+        let far_past = Utc.with_ymd_and_hms(1000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(timestamp_nanos_saturating(&far_past), i64::MIN);
+    }
+
+    #[test]
+    fn test_far_future_round_trips_through_proto() {
+        let mut data = sample();
+        data.timestamp = Utc.with_ymd_and_hms(3000, 1, 1, 0, 0, 0).unwrap();
+        let back = MarketData::try_from(MarketDataPoint::from(data.clone())).unwrap();
+        assert_eq!(back.timestamp, data.timestamp);
+    }
+
+    #[test]
+    fn test_negative_nanos_are_rejected() {
+        let ts = prost_types::Timestamp {
+            seconds: 0,
+            nanos: -1,
+        };
+        assert!(from_proto_timestamp(&ts).is_err());
+    }
+}
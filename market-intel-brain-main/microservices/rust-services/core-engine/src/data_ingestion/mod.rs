@@ -3,11 +3,17 @@
 // وحدة استيعاد البيانات
 
 pub mod service;
+pub mod market_data;
+pub mod symbol;
 pub mod sources;
 pub mod processors;
 pub mod handlers;
+pub mod sentiment;
 
 pub use service::*;
+pub use market_data::*;
+pub use symbol::*;
 pub use sources::*;
 pub use processors::*;
 pub use handlers::*;
+pub use sentiment::*;
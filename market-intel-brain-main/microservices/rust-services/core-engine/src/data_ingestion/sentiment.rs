@@ -0,0 +1,252 @@
+// Copyright (c) 2024 Market Intel Brain Team
+// Sentiment Scoring Module
+// وحدة تقييم المشاعر
+
+use std::collections::HashMap;
+use tracing::info;
+
+/// Scores below this magnitude count as neutral
+/// الدرجات التي تقل عن هذا المقدار تعتبر محايدة
+pub const NEUTRAL_BAND: f64 = 0.05;
+
+/// Scores text sentiment in [-1, 1]
+/// يقيّم مشاعر النص في النطاق [-1، 1]
+pub trait SentimentAnalyzer: Send + Sync {
+    fn score(&self, text: &str) -> f64;
+}
+
+/// Word-list analyzer with simple negation handling
+/// محلل قائم على قائمة الكلمات مع معالجة بسيطة للنفي
+#[derive(Debug, Clone)]
+pub struct LexiconSentimentAnalyzer {
+    lexicon: HashMap<String, f64>,
+}
+
+const POSITIVE_TERMS: &[(&str, f64)] = &[
+    ("gain", 1.0),
+    ("gains", 1.0),
+    ("growth", 1.0),
+    ("rally", 1.5),
+    ("rallies", 1.5),
+    ("surge", 1.5),
+    ("surges", 1.5),
+    ("soar", 2.0),
+    ("soars", 2.0),
+    ("beat", 1.0),
+    ("beats", 1.0),
+    ("upgrade", 1.5),
+    ("upgraded", 1.5),
+    ("bullish", 2.0),
+    ("optimistic", 1.5),
+    ("strong", 1.0),
+    ("record", 1.0),
+    ("profit", 1.0),
+    ("profits", 1.0),
+    ("recovery", 1.0),
+    ("outperform", 1.5),
+    ("positive", 1.0),
+];
+
+const NEGATIVE_TERMS: &[(&str, f64)] = &[
+    ("loss", -1.0),
+    ("losses", -1.0),
+    ("decline", -1.0),
+    ("declines", -1.0),
+    ("drop", -1.0),
+    ("drops", -1.0),
+    ("fall", -1.0),
+    ("falls", -1.0),
+    ("plunge", -2.0),
+    ("plunges", -2.0),
+    ("crash", -2.5),
+    ("miss", -1.0),
+    ("misses", -1.0),
+    ("downgrade", -1.5),
+    ("downgraded", -1.5),
+    ("bearish", -2.0),
+    ("pessimistic", -1.5),
+    ("weak", -1.0),
+    ("recession", -2.0),
+    ("bankruptcy", -2.5),
+    ("fraud", -2.5),
+    ("lawsuit", -1.5),
+    ("underperform", -1.5),
+    ("negative", -1.0),
+];
+
+const NEGATIONS: &[&str] = &["not", "no", "never", "without", "isn't", "wasn't", "didn't"];
+
+/// Controls how quickly the summed word weights saturate towards ±1
+const NORMALIZATION_ALPHA: f64 = 15.0;
+
+impl LexiconSentimentAnalyzer {
+    pub fn new() -> Self {
+        let lexicon = POSITIVE_TERMS
+            .iter()
+            .chain(NEGATIVE_TERMS.iter())
+            .map(|(word, weight)| (word.to_string(), *weight))
+            .collect();
+        Self { lexicon }
+    }
+
+    /// Add or override a term's weight
+    /// إضافة أو تجاوز وزن مصطلح
+    pub fn with_term(mut self, word: &str, weight: f64) -> Self {
+        self.lexicon.insert(word.to_lowercase(), weight);
+        self
+    }
+}
+
+impl Default for LexiconSentimentAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SentimentAnalyzer for LexiconSentimentAnalyzer {
+    fn score(&self, text: &str) -> f64 {
+        let mut total = 0.0;
+        let mut negate = false;
+        for token in text
+            .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+            .filter(|t| !t.is_empty())
+        {
+            let word = token.to_lowercase();
+            if NEGATIONS.contains(&word.as_str()) {
+                negate = true;
+                continue;
+            }
+            if let Some(weight) = self.lexicon.get(&word) {
+                total += if negate { -weight } else { *weight };
+            }
+            negate = false;
+        }
+
+        if total == 0.0 {
+            0.0
+        } else {
+            total / (total * total + NORMALIZATION_ALPHA).sqrt()
+        }
+    }
+}
+
+/// Source score, or a recomputed one when `recompute` is set
+/// درجة المصدر، أو درجة معاد حسابها عند تعيين `recompute`
+pub fn resolve_sentiment(
+    analyzer: &dyn SentimentAnalyzer,
+    recompute: bool,
+    source_score: f64,
+    content: &str,
+) -> f64 {
+    if recompute {
+        analyzer.score(content)
+    } else {
+        source_score
+    }
+}
+
+/// Count of positive, negative and neutral scores in a batch
+/// عدد الدرجات الإيجابية والسلبية والمحايدة في دفعة
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SentimentDistribution {
+    pub positive: usize,
+    pub negative: usize,
+    pub neutral: usize,
+    pub mean: f64,
+}
+
+impl SentimentDistribution {
+    pub fn from_scores(scores: &[f64]) -> Self {
+        let mut distribution = Self::default();
+        for &score in scores {
+            if score > NEUTRAL_BAND {
+                distribution.positive += 1;
+            } else if score < -NEUTRAL_BAND {
+                distribution.negative += 1;
+            } else {
+                distribution.neutral += 1;
+            }
+        }
+        if !scores.is_empty() {
+            distribution.mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        }
+        distribution
+    }
+
+    /// Log the distribution for a fetched batch
+    /// تسجيل التوزيع لدفعة تم جلبها
+    pub fn emit(&self, source: &str) {
+        info!(
+            source,
+            positive = self.positive,
+            negative = self.negative,
+            neutral = self.neutral,
+            mean = self.mean,
+            "News sentiment distribution"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positive_text() {
+        let analyzer = LexiconSentimentAnalyzer::new();
+        let score = analyzer.score(
+            "Technology stocks surge as investors remain optimistic about economic recovery",
+        );
+        assert!(score > NEUTRAL_BAND, "score was {}", score);
+        assert!(score <= 1.0);
+    }
+
+    #[test]
+    fn test_negative_text() {
+        let analyzer = LexiconSentimentAnalyzer::new();
+        let score = analyzer
+            .score("Shares plunge after earnings miss and a downgrade; recession fears grow");
+        assert!(score < -NEUTRAL_BAND, "score was {}", score);
+        assert!(score >= -1.0);
+    }
+
+    #[test]
+    fn test_neutral_text() {
+        let analyzer = LexiconSentimentAnalyzer::new();
+        assert_eq!(
+            analyzer.score("The company will hold its annual meeting on Tuesday"),
+            0.0
+        );
+        assert_eq!(analyzer.score(""), 0.0);
+    }
+
+    #[test]
+    fn test_negation_flips_polarity() {
+        let analyzer = LexiconSentimentAnalyzer::new();
+        assert!(analyzer.score("Analysts are not optimistic") < 0.0);
+    }
+
+    #[test]
+    fn test_score_is_bounded() {
+        let analyzer = LexiconSentimentAnalyzer::new();
+        let text = "soar ".repeat(500);
+        let score = analyzer.score(&text);
+        assert!(score > 0.99 && score <= 1.0);
+    }
+
+    #[test]
+    fn test_resolve_sentiment_respects_flag() {
+        let analyzer = LexiconSentimentAnalyzer::new();
+        assert_eq!(resolve_sentiment(&analyzer, false, 0.8, "crash"), 0.8);
+        assert!(resolve_sentiment(&analyzer, true, 0.8, "crash") < 0.0);
+    }
+
+    #[test]
+    fn test_distribution() {
+        let distribution = SentimentDistribution::from_scores(&[0.5, -0.4, 0.0, 0.02, 0.9]);
+        assert_eq!(distribution.positive, 2);
+        assert_eq!(distribution.negative, 1);
+        assert_eq!(distribution.neutral, 2);
+        assert!((distribution.mean - 0.204).abs() < 1e-9);
+    }
+}
@@ -2,6 +2,7 @@
 // Data Ingestion Service
 // خدمة استيعاد البيانات
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
@@ -17,12 +18,23 @@ pub enum DataIngestionError {
     
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
+    
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    
+    #[error("Rate limited by source: {0}")]
+    RateLimited(String),
+    
+    #[error("Source timed out: {0}")]
+    Timeout(String),
 }
 
 pub type DataIngestionResult<T> = Result<T, DataIngestionError>;
 
 pub struct DataIngestionService {
     sources: Arc<RwLock<Vec<String>>>,
+    /// Length of `sources`, readable without the lock
+    source_count: AtomicUsize,
     processors: Arc<RwLock<Vec<String>>>,
 }
 
@@ -32,25 +44,45 @@ impl DataIngestionService {
         
         Ok(Self {
             sources: Arc::new(RwLock::new(Vec::new())),
+            source_count: AtomicUsize::new(0),
             processors: Arc::new(RwLock::new(Vec::new())),
         })
     }
     
     pub async fn add_source(&self, source: String) -> DataIngestionResult<()> {
         let mut sources = self.sources.write().await;
-        sources.push(source);
         info!("Added data source: {}", source);
+        sources.push(source);
+        self.source_count.store(sources.len(), Ordering::Release);
+        Ok(())
+    }
+    
+    pub async fn remove_source(&self, source: &str) -> DataIngestionResult<()> {
+        let mut sources = self.sources.write().await;
+        let index = sources
+            .iter()
+            .position(|s| s == source)
+            .ok_or_else(|| DataIngestionError::SourceNotFound(source.to_string()))?;
+        sources.remove(index);
+        self.source_count.store(sources.len(), Ordering::Release);
+        info!("Removed data source: {}", source);
         Ok(())
     }
     
     pub async fn get_sources(&self) -> Vec<String> {
         self.sources.read().await.clone()
     }
+
+    /// Whether any source is registered. Never waits on the source list, so
+    /// it is safe to call from sync code such as readiness checks.
+    pub fn has_sources(&self) -> bool {
+        self.source_count.load(Ordering::Acquire) > 0
+    }
     
     pub async fn add_processor(&self, processor: String) -> DataIngestionResult<()> {
         let mut processors = self.processors.write().await;
-        processors.push(processor);
         info!("Added data processor: {}", processor);
+        processors.push(processor);
         Ok(())
     }
     
@@ -2,8 +2,14 @@
 // Data Sources Module
 // وحدة مصادر البيانات
 
+use opentelemetry::propagation::Injector;
+use opentelemetry::{global, Context};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::service::DataIngestionError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataSource {
@@ -41,4 +47,170 @@ impl DataSource {
         self.config = config;
         self
     }
+
+    /// Build a GET request against this source's endpoint, carrying the
+    /// current span's trace context so the upstream call joins our trace.
+    pub fn request(&self, client: &reqwest::Client) -> reqwest::RequestBuilder {
+        inject_trace_context(client.get(&self.endpoint))
+    }
+
+    /// Fetch this source's endpoint, mapping throttling and timeouts to the
+    /// retryable ingestion errors
+    pub async fn fetch(&self, client: &reqwest::Client) -> Result<reqwest::Response, DataIngestionError> {
+        let response = self.request(client).send().await.map_err(|e| {
+            if e.is_timeout() {
+                DataIngestionError::Timeout(format!("{}: {}", self.id, e))
+            } else {
+                DataIngestionError::ProcessingFailed(format!("{}: {}", self.id, e))
+            }
+        })?;
+
+        match response.status() {
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Err(DataIngestionError::RateLimited(self.id.clone())),
+            status if !status.is_success() => Err(DataIngestionError::ProcessingFailed(format!(
+                "{}: HTTP {}",
+                self.id, status
+            ))),
+            _ => Ok(response),
+        }
+    }
+}
+
+/// Adapter letting the OpenTelemetry propagator write into reqwest headers.
+pub struct HeaderInjector<'a>(pub &'a mut HeaderMap);
+
+impl<'a> Injector for HeaderInjector<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Inject `cx` into `headers` using the globally installed text map propagator.
+pub fn inject_context(cx: &Context, headers: &mut HeaderMap) {
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(cx, &mut HeaderInjector(headers))
+    });
+}
+
+/// Attach the current tracing span's context (`traceparent`/`tracestate`) to an
+/// outbound request.
+pub fn inject_trace_context(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let cx = tracing::Span::current().context();
+    let mut headers = HeaderMap::new();
+    inject_context(&cx, &mut headers);
+    builder.headers(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{TraceContextExt, Tracer, TracerProvider as _};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tracing::Instrument;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Minimal HTTP server that answers a single request with its own request
+    /// headers as the response body.
+    async fn spawn_echo_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 8192];
+            let mut read = 0;
+            loop {
+                let n = socket.read(&mut buf[read..]).await.unwrap();
+                read += n;
+                if n == 0 || buf[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+                    break;
+                }
+            }
+            let body = String::from_utf8_lossy(&buf[..read]).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_outbound_request_carries_traceparent() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = TracerProvider::builder().build();
+        let tracer = provider.tracer("data-ingestion-test");
+        let span = tracer.start("fetch");
+        let trace_id = span.span_context().trace_id();
+        let cx = Context::current_with_span(span);
+
+        let endpoint = spawn_echo_server().await;
+        let mut headers = HeaderMap::new();
+        inject_context(&cx, &mut headers);
+
+        let echoed = reqwest::Client::new()
+            .get(&endpoint)
+            .headers(headers)
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        let traceparent = echoed
+            .lines()
+            .find_map(|line| line.strip_prefix("traceparent: "))
+            .expect("traceparent header missing");
+        let parts: Vec<&str> = traceparent.trim().split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], "00");
+        assert_eq!(parts[1], format!("{:032x}", trace_id));
+        assert_eq!(parts[2].len(), 16);
+        assert_eq!(parts[3], "01");
+    }
+
+    #[tokio::test]
+    async fn test_source_fetch_carries_current_span_context() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("data-ingestion-test")));
+        let _subscriber = tracing::subscriber::set_default(subscriber);
+
+        let endpoint = spawn_echo_server().await;
+        let source = DataSource::new("echo".to_string(), "Echo".to_string(), SourceType::REST, endpoint);
+
+        let span = tracing::info_span!("ingest");
+        let trace_id = span.context().span().span_context().trace_id();
+        let client = reqwest::Client::new();
+        let response = source.fetch(&client).instrument(span).await.unwrap();
+        let echoed = response.text().await.unwrap();
+
+        let traceparent = echoed
+            .lines()
+            .find_map(|line| line.strip_prefix("traceparent: "))
+            .expect("traceparent header missing");
+        assert!(trace_id != opentelemetry::trace::TraceId::INVALID);
+        assert_eq!(traceparent.trim().split('-').nth(1), Some(format!("{:032x}", trace_id).as_str()));
+    }
+
+    #[test]
+    fn test_no_active_span_injects_nothing() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let mut headers = HeaderMap::new();
+        inject_context(&Context::new(), &mut headers);
+        assert!(headers.get("traceparent").is_none());
+    }
 }
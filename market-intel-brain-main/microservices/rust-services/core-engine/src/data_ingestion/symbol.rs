@@ -0,0 +1,132 @@
+// Copyright (c) 2024 Market Intel Brain Team
+// Ticker Symbol Type
+// نوع رمز التداول
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use super::service::DataIngestionError;
+
+/// Longest symbol accepted, covering suffixed tickers such as `BRK.B` or `7203.T`
+/// أطول رمز مقبول
+pub const MAX_SYMBOL_LEN: usize = 16;
+
+/// Validated ticker symbol: non-empty, uppercase ASCII letters, digits and dots
+/// رمز تداول تم التحقق منه: غير فارغ، أحرف كبيرة وأرقام ونقاط
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Symbol(String);
+
+impl Symbol {
+    /// Validate `raw` as a symbol. Input is not normalized, so `aapl` is rejected.
+    /// التحقق من صحة الرمز دون تعديله
+    pub fn parse(raw: &str) -> Result<Self, DataIngestionError> {
+        let invalid = |reason: &str| DataIngestionError::InvalidData(format!("invalid symbol {:?}: {}", raw, reason));
+
+        if raw.is_empty() {
+            return Err(invalid("empty"));
+        }
+        if raw.len() > MAX_SYMBOL_LEN {
+            return Err(invalid("too long"));
+        }
+        if !raw.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'.') {
+            return Err(invalid("only uppercase letters, digits and '.' are allowed"));
+        }
+        if raw.starts_with('.') || raw.ends_with('.') {
+            return Err(invalid("must not start or end with '.'"));
+        }
+
+        Ok(Self(raw.to_string()))
+    }
+
+    /// The symbol as a string slice
+    /// الرمز كنص
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = DataIngestionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Symbol {
+    type Error = DataIngestionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_symbols() {
+        for raw in ["AAPL", "BRK.B", "7203.T", "X", "SPY500"] {
+            assert_eq!(Symbol::parse(raw).unwrap(), raw);
+        }
+    }
+
+    #[test]
+    fn test_invalid_symbols() {
+        for raw in ["", "aapl", "AAPL ", " AAPL", "BTC-USD", "A/B", ".AAPL", "AAPL.", "ÄPFEL", "ABCDEFGHIJKLMNOPQ"] {
+            assert!(
+                matches!(Symbol::parse(raw), Err(DataIngestionError::InvalidData(_))),
+                "{:?} should be rejected",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let symbol = Symbol::parse("BRK.B").unwrap();
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, "\"BRK.B\"");
+        assert_eq!(serde_json::from_str::<Symbol>(&json).unwrap(), symbol);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid() {
+        assert!(serde_json::from_str::<Symbol>("\"\"").is_err());
+        assert!(serde_json::from_str::<Symbol>("\"msft\"").is_err());
+    }
+}
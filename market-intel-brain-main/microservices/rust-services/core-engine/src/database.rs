@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use std::env;
 
+use crate::errors::{EngineError, EngineResult};
+
 /// Database configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -34,7 +36,7 @@ impl Default for DatabaseConfig {
 
 impl DatabaseConfig {
     /// Load database configuration from environment variables
-    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn from_env() -> EngineResult<Self> {
         let host = env::var("DATABASE_HOST")
             .unwrap_or_else(|_| "localhost".to_string());
         
@@ -74,21 +76,21 @@ impl DatabaseConfig {
     }
 
     /// Validate database configuration
-    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn validate(&self) -> EngineResult<()> {
         if self.host.is_empty() {
-            return Err("Database host cannot be empty".into());
+            return Err(EngineError::Config("Database host cannot be empty".to_string()));
         }
         
         if self.database.is_empty() {
-            return Err("Database name cannot be empty".into());
+            return Err(EngineError::Config("Database name cannot be empty".to_string()));
         }
         
         if self.port == 0 {
-            return Err("Database port cannot be 0".into());
+            return Err(EngineError::Config("Database port cannot be 0".to_string()));
         }
         
         if self.max_connections == 0 {
-            return Err("Max connections cannot be 0".into());
+            return Err(EngineError::Config("Max connections cannot be 0".to_string()));
         }
 
         tracing::info!("Database configuration validated successfully");
@@ -109,13 +111,13 @@ impl DatabasePool {
         Self { config }
     }
 
-    pub async fn get_connection(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn get_connection(&self) -> EngineResult<()> {
         tracing::info!("Getting database connection");
         // Placeholder implementation
         Ok(())
     }
 
-    pub async fn health_check(&self) -> Result<bool, Box<dyn std::error::Error>> {
+    pub async fn health_check(&self) -> EngineResult<bool> {
         tracing::info!("Performing database health check");
         // Placeholder implementation
         Ok(true)
@@ -0,0 +1,278 @@
+//! Engine error types
+//!
+//! `EngineError` is what the public constructors and config helpers in this
+//! crate return, so callers can match on the failure instead of downcasting
+//! a `Box<dyn Error>`.
+//!
+//! Statuses built from these errors carry `google.rpc` error details: an
+//! `ErrorInfo` with a stable error code and a retryable flag, and a
+//! `RetryInfo` when the caller may retry. `into_status` also records the
+//! request's correlation id (see [`correlation_id`]) in `ErrorInfo` and a
+//! `RequestInfo`; the plain `From` conversions have no request to take it
+//! from and leave it out.
+
+use std::collections::HashMap;
+
+use opentelemetry::trace::{TraceContextExt, TraceId};
+use thiserror::Error;
+use tonic::metadata::MetadataMap;
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::data_ingestion::DataIngestionError;
+
+/// Request header a caller can set to choose the correlation id
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Errors surfaced by core-engine setup and service code.
+#[derive(Debug, Error)]
+pub enum EngineError {
+    /// Invalid or missing configuration
+    #[error("configuration error: {0}")]
+    Config(String),
+
+    /// Database connection or pool failure; bad settings are `Config`
+    #[error("database error: {0}")]
+    Database(String),
+
+    /// TLS material missing or unreadable
+    #[error("TLS error: {0}")]
+    Tls(String),
+
+    /// Filesystem or socket I/O failure
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// gRPC transport failure (bind, serve, connect)
+    #[error("transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    /// Anything else that should not reach callers as a specific variant
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+/// Result alias for core-engine operations.
+pub type EngineResult<T> = Result<T, EngineError>;
+
+/// `ErrorInfo.domain` for every status produced by this crate
+pub const ERROR_DOMAIN: &str = "core-engine.market-intel";
+
+impl EngineError {
+    /// Stable machine-readable code, used as `ErrorInfo.reason`
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            EngineError::Config(_) => "CONFIG_ERROR",
+            EngineError::Database(_) => "DATABASE_UNAVAILABLE",
+            EngineError::Tls(_) => "TLS_ERROR",
+            EngineError::Io(_) => "IO_ERROR",
+            EngineError::Transport(_) => "TRANSPORT_UNAVAILABLE",
+            EngineError::Internal(_) => "INTERNAL",
+        }
+    }
+
+    /// Whether the same request may succeed if retried
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, EngineError::Database(_) | EngineError::Transport(_))
+    }
+
+    fn status_code(&self) -> Code {
+        match self {
+            EngineError::Config(_) => Code::FailedPrecondition,
+            EngineError::Database(_) | EngineError::Transport(_) => Code::Unavailable,
+            EngineError::Tls(_) | EngineError::Io(_) | EngineError::Internal(_) => Code::Internal,
+        }
+    }
+
+    /// Convert to a `Status` whose details carry `correlation_id`
+    pub fn into_status(self, correlation_id: &str) -> Status {
+        detailed_status(self.status_code(), self.to_string(), self.error_code(), self.is_retryable(), Some(correlation_id))
+    }
+}
+
+impl DataIngestionError {
+    /// Stable machine-readable code, used as `ErrorInfo.reason`
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            DataIngestionError::SourceNotFound(_) => "SOURCE_NOT_FOUND",
+            DataIngestionError::InvalidData(_) => "INVALID_DATA",
+            DataIngestionError::RateLimited(_) => "RATE_LIMITED",
+            DataIngestionError::Timeout(_) => "TIMEOUT",
+            DataIngestionError::ConfigurationError(_) => "CONFIG_ERROR",
+            DataIngestionError::ProcessingFailed(_) => "PROCESSING_FAILED",
+        }
+    }
+
+    /// Rate limits and timeouts are transient; everything else will fail again
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, DataIngestionError::RateLimited(_) | DataIngestionError::Timeout(_))
+    }
+
+    fn status_code(&self) -> Code {
+        match self {
+            DataIngestionError::SourceNotFound(_) => Code::NotFound,
+            DataIngestionError::InvalidData(_) => Code::InvalidArgument,
+            DataIngestionError::RateLimited(_) => Code::ResourceExhausted,
+            DataIngestionError::Timeout(_) => Code::DeadlineExceeded,
+            DataIngestionError::ConfigurationError(_) => Code::FailedPrecondition,
+            DataIngestionError::ProcessingFailed(_) => Code::Internal,
+        }
+    }
+
+    /// Convert to a `Status` whose details carry `correlation_id`
+    pub fn into_status(self, correlation_id: &str) -> Status {
+        detailed_status(self.status_code(), self.to_string(), self.error_code(), self.is_retryable(), Some(correlation_id))
+    }
+}
+
+/// Conversions without a request at hand carry no correlation id
+impl From<EngineError> for Status {
+    fn from(err: EngineError) -> Self {
+        detailed_status(err.status_code(), err.to_string(), err.error_code(), err.is_retryable(), None)
+    }
+}
+
+impl From<DataIngestionError> for Status {
+    fn from(err: DataIngestionError) -> Self {
+        detailed_status(err.status_code(), err.to_string(), err.error_code(), err.is_retryable(), None)
+    }
+}
+
+/// Correlation id for a request: the caller's `x-request-id`, else the trace
+/// id of the current span (the one the request-log layer records)
+pub fn correlation_id(metadata: &MetadataMap) -> Option<String> {
+    if let Some(id) = metadata.get(REQUEST_ID_HEADER).and_then(|v| v.to_str().ok()) {
+        if !id.is_empty() {
+            return Some(id.to_string());
+        }
+    }
+    let trace_id = tracing::Span::current().context().span().span_context().trace_id();
+    (trace_id != TraceId::INVALID).then(|| trace_id.to_string())
+}
+
+/// Whether a status returned by the engine is marked retryable in its details
+pub fn is_retryable(status: &Status) -> bool {
+    let details = status.get_error_details();
+    details.retry_info().is_some()
+        || details
+            .error_info()
+            .is_some_and(|info| info.metadata.get("retryable").is_some_and(|v| v == "true"))
+}
+
+fn detailed_status(
+    code: Code,
+    message: String,
+    error_code: &str,
+    retryable: bool,
+    correlation_id: Option<&str>,
+) -> Status {
+    let mut metadata = HashMap::from([("retryable".to_string(), retryable.to_string())]);
+    if let Some(id) = correlation_id {
+        metadata.insert("correlation_id".to_string(), id.to_string());
+    }
+
+    let mut details = ErrorDetails::with_error_info(error_code, ERROR_DOMAIN, metadata);
+    if let Some(id) = correlation_id {
+        details.set_request_info(id, "");
+    }
+    if retryable {
+        details.set_retry_info(None);
+    }
+
+    Status::with_error_details(code, message, details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_converts_to_io_variant() {
+        fn read_missing() -> EngineResult<Vec<u8>> {
+            Ok(std::fs::read("/definitely/not/here")?)
+        }
+        assert!(matches!(read_missing(), Err(EngineError::Io(_))));
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        assert_eq!(Status::from(EngineError::Config("x".into())).code(), tonic::Code::FailedPrecondition);
+        assert_eq!(Status::from(EngineError::Database("connection refused".into())).code(), tonic::Code::Unavailable);
+        assert_eq!(Status::from(EngineError::Internal("x".into())).code(), tonic::Code::Internal);
+    }
+
+    #[test]
+    fn test_data_ingestion_status_mapping() {
+        let code = |err| Status::from(err).code();
+        assert_eq!(code(DataIngestionError::SourceNotFound("x".into())), tonic::Code::NotFound);
+        assert_eq!(code(DataIngestionError::InvalidData("x".into())), tonic::Code::InvalidArgument);
+        assert_eq!(code(DataIngestionError::RateLimited("x".into())), tonic::Code::ResourceExhausted);
+        assert_eq!(code(DataIngestionError::Timeout("x".into())), tonic::Code::DeadlineExceeded);
+        assert_eq!(code(DataIngestionError::ProcessingFailed("x".into())), tonic::Code::Internal);
+    }
+
+    #[test]
+    fn test_rate_limit_surfaces_retryable_detail() {
+        let status = DataIngestionError::RateLimited("alpha_vantage".into()).into_status("req-42");
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert!(is_retryable(&status));
+
+        let details = status.get_error_details();
+        let info = details.error_info().expect("error info");
+        assert_eq!(info.reason, "RATE_LIMITED");
+        assert_eq!(info.domain, ERROR_DOMAIN);
+        assert_eq!(info.metadata["retryable"], "true");
+        assert_eq!(info.metadata["correlation_id"], "req-42");
+        assert_eq!(details.request_info().expect("request info").request_id, "req-42");
+        assert!(details.retry_info().is_some());
+    }
+
+    #[test]
+    fn test_from_conversion_has_no_correlation_id() {
+        let status = Status::from(EngineError::Config("x".into()));
+        let details = status.get_error_details();
+        assert!(!details.error_info().expect("error info").metadata.contains_key("correlation_id"));
+        assert!(details.request_info().is_none());
+    }
+
+    #[test]
+    fn test_correlation_id_from_request_id_header() {
+        let mut metadata = MetadataMap::new();
+        assert_eq!(correlation_id(&metadata), None);
+
+        metadata.insert(REQUEST_ID_HEADER, "req-7".parse().unwrap());
+        assert_eq!(correlation_id(&metadata).as_deref(), Some("req-7"));
+    }
+
+    #[test]
+    fn test_retryable_mapping() {
+        assert!(is_retryable(&Status::from(DataIngestionError::Timeout("x".into()))));
+        assert!(is_retryable(&Status::from(EngineError::Database("pool exhausted".into()))));
+        assert!(!is_retryable(&Status::from(DataIngestionError::InvalidData("x".into()))));
+        assert!(!is_retryable(&Status::from(EngineError::Config("x".into()))));
+        assert!(!is_retryable(&Status::internal("no details")));
+    }
+
+    #[test]
+    fn test_database_validation_is_a_config_error() {
+        let config = crate::database::DatabaseConfig { port: 0, ..Default::default() };
+        let err = config.validate().unwrap_err();
+        assert!(matches!(err, EngineError::Config(_)));
+
+        let status = Status::from(err);
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert!(!is_retryable(&status));
+    }
+
+    #[test]
+    fn test_tls_validation_variant() {
+        let config = crate::tls::TlsConfig {
+            cert_path: None,
+            key_path: None,
+            ca_cert_path: None,
+            tls_enabled: true,
+        };
+        assert!(matches!(config.validate(), Err(EngineError::Tls(_))));
+    }
+}
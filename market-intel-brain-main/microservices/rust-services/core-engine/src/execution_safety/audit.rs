@@ -0,0 +1,176 @@
+// Copyright (c) 2024 Market Intel Brain Team
+// Execution Mode Audit Trail - Phase 21.5 Task B
+// سجل مراجعة أنماط التنفيذ - المهمة 21.5 ب
+
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::warn;
+
+use super::execution_mode::ExecutionMode;
+
+/// One execution mode transition as written to the audit trail
+/// انتقال نمط تنفيذ واحد كما يُكتب في سجل المراجعة
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeAuditEntry {
+    /// Mode before the transition
+    /// النمط قبل الانتقال
+    pub from: ExecutionMode,
+
+    /// Mode after the transition
+    /// النمط بعد الانتقال
+    pub to: ExecutionMode,
+
+    /// Why the mode was changed
+    /// سبب تغيير النمط
+    pub reason: String,
+
+    /// Who initiated the change
+    /// من بدأ التغيير
+    pub actor: String,
+
+    /// When the change took effect
+    /// وقت سريان التغيير
+    pub timestamp: DateTime<Utc>,
+
+    /// Who approved the change, if anyone did
+    /// من وافق على التغيير إن وجد
+    pub approved_by: Option<String>,
+}
+
+/// Append-only JSONL audit trail of execution mode transitions
+/// سجل مراجعة بصيغة JSONL للإلحاق فقط لانتقالات أنماط التنفيذ
+#[derive(Debug)]
+pub struct ModeAuditLog {
+    /// Path of the JSONL file
+    /// مسار ملف JSONL
+    path: PathBuf,
+
+    /// Append handle, opened on first use; the lock serializes appends so
+    /// concurrent transitions never interleave lines
+    /// مقبض الإلحاق يُفتح عند أول استخدام؛ القفل يسلسل عمليات الإلحاق
+    file: tokio::sync::Mutex<Option<tokio::fs::File>>,
+}
+
+impl ModeAuditLog {
+    /// Create an audit log at `path`; the file is created on first append
+    /// إنشاء سجل مراجعة في المسار المحدد؛ يُنشأ الملف عند أول إلحاق
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            file: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Path of the underlying file
+    /// مسار الملف الأساسي
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one entry and flush it to disk
+    /// إلحاق إدخال واحد وحفظه على القرص
+    pub async fn append(&self, entry: &ModeAuditEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        line.push('\n');
+
+        let mut guard = self.file.lock().await;
+        let file = match guard.take() {
+            Some(file) => guard.insert(file),
+            None => guard.insert(
+                tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .await?,
+            ),
+        };
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        file.sync_data().await
+    }
+
+    /// Entries with `from_ts <= timestamp <= to_ts`, in file order.
+    /// A missing file is an empty trail; unparseable lines are skipped.
+    /// الإدخالات ضمن النطاق الزمني بترتيب الملف
+    pub async fn query(&self, from_ts: DateTime<Utc>, to_ts: DateTime<Utc>) -> std::io::Result<Vec<ModeAuditEntry>> {
+        let file = match tokio::fs::File::open(&self.path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = Vec::new();
+        let mut lines = BufReader::new(file).lines();
+        let mut index = 0;
+        while let Some(line) = lines.next_line().await? {
+            index += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<ModeAuditEntry>(&line) {
+                Ok(entry) if entry.timestamp >= from_ts && entry.timestamp <= to_ts => entries.push(entry),
+                Ok(_) => {}
+                Err(e) => warn!("Skipping malformed audit line {} in {:?}: {}", index, self.path, e),
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    fn entry(to: ExecutionMode, timestamp: DateTime<Utc>) -> ModeAuditEntry {
+        ModeAuditEntry {
+            from: ExecutionMode::DryRun,
+            to,
+            reason: "test".to_string(),
+            actor: "operator".to_string(),
+            timestamp,
+            approved_by: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_filters_by_time_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = ModeAuditLog::new(dir.path().join("audit.jsonl"));
+        let base = Utc::now();
+
+        log.append(&entry(ExecutionMode::Backtest, base - Duration::hours(2))).await.unwrap();
+        log.append(&entry(ExecutionMode::DryRun, base - Duration::hours(1))).await.unwrap();
+        log.append(&entry(ExecutionMode::EmergencyStop, base)).await.unwrap();
+
+        let recent = log.query(base - Duration::minutes(90), base).await.unwrap();
+        let modes: Vec<_> = recent.iter().map(|e| e.to).collect();
+        assert_eq!(modes, vec![ExecutionMode::DryRun, ExecutionMode::EmergencyStop]);
+
+        assert_eq!(log.query(base - Duration::days(1), base).await.unwrap().len(), 3);
+        assert!(log.query(base + Duration::seconds(1), base + Duration::hours(1)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_and_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = ModeAuditLog::new(&path);
+        let range = (Utc::now() - Duration::days(1), Utc::now() + Duration::days(1));
+
+        assert!(log.query(range.0, range.1).await.unwrap().is_empty());
+
+        log.append(&entry(ExecutionMode::Backtest, Utc::now())).await.unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"from\": \"DryRun\", \"to\"\n").unwrap();
+
+        assert_eq!(log.query(range.0, range.1).await.unwrap().len(), 1);
+    }
+}
@@ -22,6 +22,10 @@ pub enum ExecutionMode {
     /// Backtest mode - Fast-forward purely on historical data
     /// نمط الاختبار الخلفي - التقدم السريع فقط على البيانات التاريخية
     Backtest,
+    
+    /// EmergencyStop mode - Kill switch, no order may be executed
+    /// نمط التوقف الطارئ - مفتاح الإيقاف، لا يجوز تنفيذ أي أمر
+    EmergencyStop,
 }
 
 impl ExecutionMode {
@@ -32,6 +36,7 @@ impl ExecutionMode {
             ExecutionMode::Live => "live",
             ExecutionMode::DryRun => "dry_run",
             ExecutionMode::Backtest => "backtest",
+            ExecutionMode::EmergencyStop => "emergency_stop",
         }
     }
 
@@ -42,6 +47,7 @@ impl ExecutionMode {
             ExecutionMode::Live => "Live Trading",
             ExecutionMode::DryRun => "Dry Run Simulation",
             ExecutionMode::Backtest => "Backtest Mode",
+            ExecutionMode::EmergencyStop => "Emergency Stop",
         }
     }
 
@@ -52,6 +58,36 @@ impl ExecutionMode {
             ExecutionMode::Live => "Real money trading with actual market execution",
             ExecutionMode::DryRun => "Simulated execution with real-time data, no actual trades",
             ExecutionMode::Backtest => "Historical data analysis with fast-forward simulation",
+            ExecutionMode::EmergencyStop => "All order execution halted until an operator resumes",
+        }
+    }
+
+    /// Check if the mode is the emergency kill switch
+    /// التحقق مما إذا كان النمط هو مفتاح الإيقاف الطارئ
+    pub fn is_emergency_stop(&self) -> bool {
+        matches!(self, ExecutionMode::EmergencyStop)
+    }
+
+    /// Compact tag for lock-free storage in an `AtomicU8`
+    /// وسم مضغوط للتخزين بدون أقفال في `AtomicU8`
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            ExecutionMode::Live => 0,
+            ExecutionMode::DryRun => 1,
+            ExecutionMode::Backtest => 2,
+            ExecutionMode::EmergencyStop => 3,
+        }
+    }
+
+    /// Inverse of [`ExecutionMode::as_u8`]
+    /// عكس [`ExecutionMode::as_u8`]
+    pub fn from_u8(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(ExecutionMode::Live),
+            1 => Some(ExecutionMode::DryRun),
+            2 => Some(ExecutionMode::Backtest),
+            3 => Some(ExecutionMode::EmergencyStop),
+            _ => None,
         }
     }
 
@@ -92,6 +128,7 @@ impl ExecutionMode {
             ExecutionMode::Live => RiskLevel::High,
             ExecutionMode::DryRun => RiskLevel::Low,
             ExecutionMode::Backtest => RiskLevel::None,
+            ExecutionMode::EmergencyStop => RiskLevel::None,
         }
     }
 
@@ -115,6 +152,8 @@ impl ExecutionMode {
                 Permission::Simulation,
                 Permission::Analysis,
             ],
+            // Entering the kill switch must never be blocked on permissions
+            ExecutionMode::EmergencyStop => vec![],
         }
     }
 
@@ -139,6 +178,7 @@ impl ExecutionMode {
                 MonitoringRequirement::PerformanceAnalysis,
                 MonitoringRequirement::ResultReporting,
             ],
+            ExecutionMode::EmergencyStop => vec![],
         }
     }
 
@@ -162,6 +202,7 @@ impl ExecutionMode {
                 DataSourceRequirement::HistoricalTradeData,
                 DataSourceRequirement::HistoricalAccountData,
             ],
+            ExecutionMode::EmergencyStop => vec![],
         }
     }
 
@@ -210,6 +251,8 @@ impl ExecutionMode {
             uses_historical_data: self.uses_historical_data(),
             risk_level: self.risk_level(),
             timestamp: Utc::now(),
+            mfa_token: None,
+            approval_token: None,
         }
     }
 }
@@ -228,7 +271,10 @@ impl Default for ExecutionMode {
 
 /// Risk Level Enum
 /// تعداد مستوى المخاطر
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Variants are declared from least to most risky; the derived ordering relies on it.
+/// المتغيرات مرتبة من الأقل إلى الأكثر خطورة.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum RiskLevel {
     /// No risk - Historical analysis only
     /// لا مخاطر - تحليل تاريخي فقط
@@ -245,6 +291,10 @@ pub enum RiskLevel {
     /// High risk - Full real trading exposure
     /// مخاطر عالية - تعرض تداول حقيقي كامل
     High,
+    
+    /// Critical risk - Exposure beyond what any environment allows by default
+    /// مخاطر حرجة - تعرض يتجاوز ما تسمح به أي بيئة افتراضياً
+    Critical,
 }
 
 impl RiskLevel {
@@ -256,6 +306,29 @@ impl RiskLevel {
             RiskLevel::Low => 1,
             RiskLevel::Medium => 2,
             RiskLevel::High => 3,
+            RiskLevel::Critical => 4,
+        }
+    }
+
+    /// Check if this risk level is strictly above `other`
+    /// التحقق مما إذا كان مستوى المخاطر هذا أعلى من `other`
+    pub fn exceeds(&self, other: &RiskLevel) -> bool {
+        self > other
+    }
+
+    /// Map a normalized risk score (0.0 to 1.0) onto a level
+    /// تحويل درجة مخاطر (0.0 إلى 1.0) إلى مستوى
+    pub fn from_score(score: f64) -> RiskLevel {
+        if score <= 0.0 {
+            RiskLevel::None
+        } else if score < 0.33 {
+            RiskLevel::Low
+        } else if score < 0.66 {
+            RiskLevel::Medium
+        } else if score <= 0.9 {
+            RiskLevel::High
+        } else {
+            RiskLevel::Critical
         }
     }
 
@@ -267,6 +340,7 @@ impl RiskLevel {
             RiskLevel::Low => "blue",
             RiskLevel::Medium => "orange",
             RiskLevel::High => "red",
+            RiskLevel::Critical => "purple",
         }
     }
 
@@ -278,6 +352,7 @@ impl RiskLevel {
             RiskLevel::Low => "Low financial risk - simulation without real money",
             RiskLevel::Medium => "Medium financial risk - limited real exposure",
             RiskLevel::High => "High financial risk - full real trading exposure",
+            RiskLevel::Critical => "Critical financial risk - exposure above production limits",
         }
     }
 }
@@ -619,6 +694,33 @@ pub struct ExecutionContext {
     /// Timestamp when context was created
     /// وقت إنشاء السياق
     pub timestamp: DateTime<Utc>,
+    
+    /// MFA token presented by the caller; never serialized
+    /// رمز المصادقة متعددة العوامل المقدم من المستدعي؛ لا يُسلسل أبدًا
+    #[serde(default, skip_serializing)]
+    pub mfa_token: Option<String>,
+    
+    /// Approval token for modes that need sign-off, checked by the manager's
+    /// approval verifier; never serialized
+    /// رمز الموافقة للأنماط التي تتطلب موافقة، يتحقق منه مدقق الموافقات؛ لا يُسلسل أبدًا
+    #[serde(default, skip_serializing)]
+    pub approval_token: Option<String>,
+}
+
+impl ExecutionContext {
+    /// Attach an MFA token for checks that require one
+    /// إرفاق رمز مصادقة متعددة العوامل للفحوصات التي تتطلبه
+    pub fn with_mfa_token(mut self, token: impl Into<String>) -> Self {
+        self.mfa_token = Some(token.into());
+        self
+    }
+
+    /// Attach an approval token for transitions that need sign-off
+    /// إرفاق رمز موافقة للانتقالات التي تتطلب موافقة
+    pub fn with_approval_token(mut self, token: impl Into<String>) -> Self {
+        self.approval_token = Some(token.into());
+        self
+    }
 }
 
 /// Execution Mode Error
@@ -646,6 +748,12 @@ pub enum ExecutionModeError {
     #[error("Execution mode transition not allowed: {from:?} to {to:?}")]
     InvalidTransition { from: ExecutionMode, to: ExecutionMode },
     
+    #[error("Notional exposure for {symbol} would be {notional:.2}, exceeding limit {limit:.2}")]
+    NotionalLimitExceeded { symbol: String, notional: f64, limit: f64 },
+    
+    #[error("Invalid order parameters: {0}")]
+    InvalidOrder(String),
+    
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
 }
@@ -687,6 +795,29 @@ mod tests {
         assert_eq!(RiskLevel::Low.value(), 1);
         assert_eq!(RiskLevel::Medium.value(), 2);
         assert_eq!(RiskLevel::High.value(), 3);
+        assert_eq!(RiskLevel::Critical.value(), 4);
+    }
+
+    #[test]
+    fn test_risk_level_ordering() {
+        assert!(RiskLevel::None < RiskLevel::Low);
+        assert!(RiskLevel::Low < RiskLevel::Medium);
+        assert!(RiskLevel::Medium < RiskLevel::High);
+        assert_eq!(
+            [RiskLevel::High, RiskLevel::None, RiskLevel::Medium, RiskLevel::Low].iter().max(),
+            Some(&RiskLevel::High)
+        );
+
+        assert!(RiskLevel::High.exceeds(&RiskLevel::Medium));
+        assert!(!RiskLevel::Medium.exceeds(&RiskLevel::Medium));
+        assert!(!RiskLevel::Low.exceeds(&RiskLevel::High));
+
+        assert_eq!(RiskLevel::from_score(0.0), RiskLevel::None);
+        assert_eq!(RiskLevel::from_score(0.2), RiskLevel::Low);
+        assert_eq!(RiskLevel::from_score(0.5), RiskLevel::Medium);
+        assert_eq!(RiskLevel::from_score(0.9), RiskLevel::High);
+        assert_eq!(RiskLevel::from_score(0.95), RiskLevel::Critical);
+        assert!(RiskLevel::Critical.exceeds(&RiskLevel::High));
     }
 
     #[test]
@@ -743,6 +874,18 @@ mod tests {
         assert_eq!(format!("{}", ExecutionMode::Live), "live");
         assert_eq!(format!("{}", ExecutionMode::DryRun), "dry_run");
         assert_eq!(format!("{}", ExecutionMode::Backtest), "backtest");
+        assert_eq!(format!("{}", ExecutionMode::EmergencyStop), "emergency_stop");
+    }
+
+    #[test]
+    fn test_emergency_stop_mode() {
+        let mode = ExecutionMode::EmergencyStop;
+        assert!(mode.is_emergency_stop());
+        assert!(!mode.allows_real_execution());
+        assert!(!mode.allows_real_time_data());
+        assert!(mode.is_production_safe());
+        assert_eq!(mode.risk_level(), RiskLevel::None);
+        assert!(mode.validate_requirements(&ExecutionRequirements::default()).is_ok());
     }
 
     #[test]
@@ -6,8 +6,10 @@ pub mod execution_mode;
 pub mod safety_manager;
 pub mod safety_guards;
 pub mod monitoring;
+pub mod audit;
 
 pub use execution_mode::*;
 pub use safety_manager::*;
 pub use safety_guards::*;
 pub use monitoring::*;
+pub use audit::*;
@@ -3,7 +3,7 @@
 // مراقبة وتسجيل نمط التنفيذ - المهمة 21.5 ب
 
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -13,8 +13,8 @@ use thiserror::Error;
 use super::execution_mode::{
     ExecutionMode, ExecutionModeResult, ExecutionModeError, ExecutionContext, RiskLevel
 };
-use super::safety_manager::{ExecutionModeEvent, SafetyManagerStatistics};
-use super::safety_guards::{GuardExecutionRecord, GuardStatistics};
+use super::safety_manager::{ExecutionModeEvent, ExecutionModeEventType, SafetyManagerStatistics};
+use super::safety_guards::{GuardExecutionRecord, GuardStatistics, Operation, EMERGENCY_STOP_GUARD_NAME};
 
 /// Execution Mode Monitor
 /// مراقب نمط التنفيذ
@@ -34,6 +34,85 @@ pub struct ExecutionModeMonitor {
     /// Alert manager
     /// مدير التنبيهات
     alert_manager: Arc<AlertManager>,
+    
+    /// Rolling-window counters backing the alert thresholds
+    /// عدادات النافذة المتحركة لعتبات التنبيه
+    threshold_state: Arc<RwLock<ThresholdState>>,
+    
+    /// Operations intercepted in a non-executing mode
+    /// العمليات المعترضة في نمط لا ينفذ فعليًا
+    simulated_operations: Arc<RwLock<VecDeque<SimulatedOperation>>>,
+}
+
+/// An operation that passed its guards but was logged instead of executed
+/// عملية اجتازت الحراس لكنها سُجلت بدلاً من تنفيذها
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedOperation {
+    /// The intended operation
+    /// العملية المقصودة
+    pub operation: Operation,
+    
+    /// Mode that intercepted it
+    /// النمط الذي اعترضها
+    pub mode: ExecutionMode,
+    
+    /// When it was intercepted
+    /// وقت الاعتراض
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Events seen within a trailing time window
+/// الأحداث المرصودة ضمن نافذة زمنية متحركة
+#[derive(Debug, Clone)]
+struct RollingWindow {
+    window: chrono::Duration,
+    timestamps: VecDeque<DateTime<Utc>>,
+}
+
+impl RollingWindow {
+    fn new(window: chrono::Duration) -> Self {
+        Self { window, timestamps: VecDeque::new() }
+    }
+
+    fn record(&mut self, timestamp: DateTime<Utc>) {
+        self.timestamps.push_back(timestamp);
+    }
+
+    /// Drop entries older than the window and return how many remain
+    fn count(&mut self, now: DateTime<Utc>) -> usize {
+        let cutoff = now - self.window;
+        self.timestamps.retain(|t| *t > cutoff);
+        self.timestamps.len()
+    }
+}
+
+/// Threshold kinds evaluated over the rolling window
+/// أنواع العتبات المقيمة عبر النافذة المتحركة
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ThresholdKind {
+    FailedTransitions,
+    EmergencyStops,
+    ErrorRate,
+}
+
+#[derive(Debug, Clone)]
+struct ThresholdState {
+    transitions: RollingWindow,
+    failed_transitions: RollingWindow,
+    emergency_stops: RollingWindow,
+    last_alerted: HashMap<ThresholdKind, DateTime<Utc>>,
+}
+
+impl Default for ThresholdState {
+    fn default() -> Self {
+        let hour = chrono::Duration::hours(1);
+        Self {
+            transitions: RollingWindow::new(hour),
+            failed_transitions: RollingWindow::new(hour),
+            emergency_stops: RollingWindow::new(hour),
+            last_alerted: HashMap::new(),
+        }
+    }
 }
 
 /// Monitor Configuration
@@ -100,6 +179,15 @@ pub struct AlertThresholds {
     /// Maximum error rate threshold (0.0 to 1.0)
     /// عتبة معدل الخطأ الأقصى (0.0 إلى 1.0)
     pub max_error_rate: f64,
+    
+    /// Transitions needed in the window before the error rate is judged
+    /// عدد الانتقالات المطلوب في النافذة قبل تقييم معدل الخطأ
+    #[serde(default = "default_min_transitions_for_error_rate")]
+    pub min_transitions_for_error_rate: u32,
+}
+
+fn default_min_transitions_for_error_rate() -> u32 {
+    20
 }
 
 impl Default for AlertThresholds {
@@ -111,6 +199,7 @@ impl Default for AlertThresholds {
             min_confidence_threshold: 0.7,
             max_response_time_ms: 1000,
             max_error_rate: 0.05, // 5%
+            min_transitions_for_error_rate: default_min_transitions_for_error_rate(),
         }
     }
 }
@@ -166,6 +255,16 @@ pub struct ExecutionModeMetrics {
     /// توزيع مستوى المخاطر
     pub risk_level_distribution: HashMap<RiskLevel, u64>,
     
+    /// Operations actually executed
+    /// العمليات المنفذة فعليًا
+    #[serde(default)]
+    pub real_operations: u64,
+    
+    /// Operations logged instead of executed (DryRun)
+    /// العمليات المسجلة بدلاً من تنفيذها (التشغيل الجاف)
+    #[serde(default)]
+    pub simulated_operations: u64,
+    
     /// Performance metrics
     /// مقاييس الأداء
     pub performance_metrics: PerformanceMetrics,
@@ -232,6 +331,10 @@ pub struct SecurityMetrics {
     /// العمليات المحظورة
     pub blocked_operations: u64,
     
+    /// Operations blocked by the emergency stop kill switch
+    /// العمليات المحظورة بواسطة مفتاح الإيقاف الطارئ
+    pub emergency_stop_blocks: u64,
+    
     /// Security violations
     /// انتهاكات الأمان
     pub security_violations: u64,
@@ -241,6 +344,10 @@ pub struct SecurityMetrics {
     pub audit_log_entries: u64,
 }
 
+/// Failed webhook deliveries kept for inspection
+/// الحد الأقصى لعمليات التسليم الفاشلة المحفوظة
+const MAX_DELIVERY_FAILURES: usize = 1000;
+
 /// Alert Manager
 /// مدير التنبيهات
 pub struct AlertManager {
@@ -255,6 +362,18 @@ pub struct AlertManager {
     /// Alert history
     /// سجل التنبيهات
     alert_history: Arc<RwLock<Vec<Alert>>>,
+    
+    /// Last webhook dispatch per alert type, used for cooldown
+    /// آخر إرسال webhook لكل نوع تنبيه، يستخدم لفترة التبريد
+    last_dispatched: Arc<RwLock<HashMap<AlertType, DateTime<Utc>>>>,
+    
+    /// Most recent failed webhook deliveries, capped at `MAX_DELIVERY_FAILURES`
+    /// أحدث عمليات تسليم webhook الفاشلة
+    delivery_failures: Arc<RwLock<VecDeque<DeliveryFailure>>>,
+    
+    /// HTTP client for webhook delivery
+    /// عميل HTTP لتسليم webhook
+    http_client: reqwest::Client,
 }
 
 /// Alert Configuration
@@ -304,6 +423,27 @@ impl Default for AlertConfig {
     }
 }
 
+/// Webhook Delivery Failure
+/// فشل تسليم webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryFailure {
+    /// Alert ID
+    /// معرف التنبيه
+    pub alert_id: String,
+    
+    /// Webhook URL
+    /// عنوان webhook
+    pub url: String,
+    
+    /// Error description
+    /// وصف الخطأ
+    pub error: String,
+    
+    /// Failure timestamp
+    /// وقت الفشل
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Alert
 /// تنبيه
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -434,14 +574,28 @@ impl ExecutionModeMonitor {
     /// Create new execution mode monitor
     /// إنشاء مراقب نمط تنفيذ جديد
     pub fn new(config: MonitorConfig) -> Self {
+        Self::with_alert_config(config, AlertConfig::default())
+    }
+
+    /// Create monitor with a custom alert configuration
+    /// إنشاء مراقب مع تكوين تنبيهات مخصص
+    pub fn with_alert_config(config: MonitorConfig, alert_config: AlertConfig) -> Self {
         Self {
             config,
             event_history: Arc::new(RwLock::new(Vec::new())),
             metrics: Arc::new(RwLock::new(ExecutionModeMetrics::default())),
-            alert_manager: Arc::new(AlertManager::new(AlertConfig::default())),
+            alert_manager: Arc::new(AlertManager::new(alert_config)),
+            threshold_state: Arc::new(RwLock::new(ThresholdState::default())),
+            simulated_operations: Arc::new(RwLock::new(VecDeque::new())),
         }
     }
 
+    /// Get the alert manager
+    /// الحصول على مدير التنبيهات
+    pub fn alert_manager(&self) -> Arc<AlertManager> {
+        self.alert_manager.clone()
+    }
+
     /// Record execution mode event
     /// تسجيل حدث نمط التنفيذ
     pub async fn record_event(&self, event: ExecutionModeEvent) -> MonitorResult<()> {
@@ -603,14 +757,15 @@ impl ExecutionModeMonitor {
         metrics.total_transitions += 1;
         
         match event.event_type {
-            super::execution_mode::ExecutionModeEventType::ModeChanged => {
+            ExecutionModeEventType::ModeChanged => {
                 metrics.successful_transitions += 1;
             }
-            super::execution_mode::ExecutionModeEventType::EmergencyStopTriggered => {
+            ExecutionModeEventType::EmergencyStopTriggered => {
                 metrics.emergency_stops += 1;
             }
-            super::execution_mode::ExecutionModeEventType::SafetyCheckFailed |
-            super::execution_mode::ExecutionModeEventType::ValidationFailed => {
+            ExecutionModeEventType::SafetyCheckFailed |
+            ExecutionModeEventType::ValidationFailed |
+            ExecutionModeEventType::TransitionRateLimited => {
                 metrics.failed_transitions += 1;
             }
             _ => {}
@@ -630,6 +785,67 @@ impl ExecutionModeMonitor {
         Ok(())
     }
 
+    /// Record an operation that was really executed
+    /// تسجيل عملية نُفذت فعليًا
+    pub async fn record_real_operation(&self, operation: &Operation, mode: ExecutionMode) {
+        debug!("Executed operation {} in {}", operation.id, mode);
+        if self.config.enable_metrics_collection {
+            let mut metrics = self.metrics.write().await;
+            metrics.real_operations += 1;
+            metrics.last_updated = Utc::now();
+        }
+    }
+
+    /// Record the intent of an operation that was intercepted instead of executed
+    /// تسجيل نية عملية تم اعتراضها بدلاً من تنفيذها
+    pub async fn record_simulated_operation(&self, operation: &Operation, mode: ExecutionMode) {
+        {
+            let mut simulated = self.simulated_operations.write().await;
+            simulated.push_back(SimulatedOperation {
+                operation: operation.clone(),
+                mode,
+                timestamp: Utc::now(),
+            });
+            while simulated.len() > self.config.max_event_history {
+                simulated.pop_front();
+            }
+        }
+        
+        if self.config.enable_metrics_collection {
+            let mut metrics = self.metrics.write().await;
+            metrics.simulated_operations += 1;
+            metrics.last_updated = Utc::now();
+        }
+    }
+
+    /// Most recent simulated operations, newest first
+    /// أحدث العمليات المحاكاة، الأحدث أولاً
+    pub async fn get_simulated_operations(&self, limit: Option<usize>) -> Vec<SimulatedOperation> {
+        let simulated = self.simulated_operations.read().await;
+        let limit = limit.unwrap_or(simulated.len());
+        
+        simulated.iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Count an MFA challenge, and a blocked operation when it wasn't passed
+    /// احتساب تحدي المصادقة متعددة العوامل وعملية محظورة عند فشله
+    pub async fn record_mfa_challenge(&self, verified: bool) {
+        if !self.config.enable_metrics_collection {
+            return;
+        }
+        
+        let mut metrics = self.metrics.write().await;
+        metrics.security_metrics.mfa_challenges += 1;
+        if !verified {
+            metrics.security_metrics.blocked_operations += 1;
+        }
+        metrics.last_updated = Utc::now();
+    }
+
     /// Update metrics for guard execution
     /// تحديث المقاييس لتنفيذ الحارس
     async fn update_metrics_for_guard_execution(&self, record: &GuardExecutionRecord) -> MonitorResult<()> {
@@ -644,17 +860,20 @@ impl ExecutionModeMonitor {
         
         // Update risk level distribution
         for result in &record.guard_results {
-            let risk_level = result.risk_assessment.risk_score;
-            let risk_category = if risk_level < 0.33 {
-                RiskLevel::Low
-            } else if risk_level < 0.66 {
-                RiskLevel::Medium
-            } else {
-                RiskLevel::High
-            };
+            // Zero scores stay in the Low bucket as before
+            let risk_category = RiskLevel::from_score(result.risk_assessment.risk_score).max(RiskLevel::Low);
             *metrics.risk_level_distribution.entry(risk_category).or_insert(0) += 1;
         }
         
+        if matches!(record.overall_decision, super::safety_guards::GuardDecision::Deny) {
+            metrics.security_metrics.blocked_operations += 1;
+            
+            if record.guard_results.iter().any(|r| !r.allowed && r.guard_name == EMERGENCY_STOP_GUARD_NAME) {
+                metrics.security_metrics.emergency_stop_blocks += 1;
+                warn!("Operation {} blocked by emergency stop", record.operation.id);
+            }
+        }
+        
         // Update last updated timestamp
         metrics.last_updated = Utc::now();
         
@@ -667,7 +886,7 @@ impl ExecutionModeMonitor {
         let thresholds = &self.config.alert_thresholds;
         
         // Check for emergency stops
-        if matches!(event.event_type, super::execution_mode::ExecutionModeEventType::EmergencyStopTriggered) {
+        if matches!(event.event_type, ExecutionModeEventType::EmergencyStopTriggered) {
             self.create_alert(
                 AlertType::EmergencyStopTriggered,
                 AlertSeverity::Critical,
@@ -677,20 +896,45 @@ impl ExecutionModeMonitor {
             ).await?;
         }
         
-        // Check for failed transitions
-        if matches!(event.event_type, super::execution_mode::ExecutionModeEventType::SafetyCheckFailed) {
+        // Mode flapping: the safety manager refused a transition and is cooling down
+        if matches!(event.event_type, ExecutionModeEventType::TransitionRateLimited) {
             self.create_alert(
-                AlertType::ModeTransitionFailure,
+                AlertType::Custom("transition_rate".to_string()),
                 AlertSeverity::High,
-                "Safety check failed during mode transition".to_string(),
+                format!("Mode transitions rate-limited while in {:?}", event.mode),
                 "execution_safety".to_string(),
                 event.data.clone(),
             ).await?;
         }
         
+        // Check rolling-window thresholds
+        for (kind, observed) in self.evaluate_thresholds(event).await {
+            let (alert_type, severity, message) = match kind {
+                ThresholdKind::FailedTransitions => (
+                    AlertType::ModeTransitionFailure,
+                    AlertSeverity::High,
+                    format!("{} failed transitions in the last hour (max {})",
+                            observed, thresholds.max_failed_transitions_per_hour),
+                ),
+                ThresholdKind::EmergencyStops => (
+                    AlertType::Custom("emergency_stop_rate".to_string()),
+                    AlertSeverity::Critical,
+                    format!("{} emergency stops in the last hour (max {})",
+                            observed, thresholds.max_emergency_stops_per_hour),
+                ),
+                ThresholdKind::ErrorRate => (
+                    AlertType::Custom("error_rate".to_string()),
+                    AlertSeverity::High,
+                    format!("Transition error rate {:.1}% over the last hour (max {:.1}%)",
+                            observed * 100.0, thresholds.max_error_rate * 100.0),
+                ),
+            };
+            self.create_alert(alert_type, severity, message, "execution_safety".to_string(), event.data.clone()).await?;
+        }
+        
         // Check for high risk operations
         if event.mode == ExecutionMode::Live && 
-           matches!(event.event_type, super::execution_mode::ExecutionModeEventType::ModeChanged) {
+           matches!(event.event_type, ExecutionModeEventType::ModeChanged) {
             self.create_alert(
                 AlertType::HighRiskOperation,
                 AlertSeverity::Medium,
@@ -703,6 +947,56 @@ impl ExecutionModeMonitor {
         Ok(())
     }
 
+    /// Record the event in the rolling windows and return the thresholds that
+    /// are exceeded and not still cooling down from a previous alert
+    /// تسجيل الحدث في النوافذ المتحركة وإرجاع العتبات المتجاوزة
+    async fn evaluate_thresholds(&self, event: &ExecutionModeEvent) -> Vec<(ThresholdKind, f64)> {
+        let thresholds = &self.config.alert_thresholds;
+        let cooldown = chrono::Duration::minutes(self.alert_manager.config.alert_cooldown_minutes as i64);
+        let now = Utc::now();
+        let mut state = self.threshold_state.write().await;
+        
+        // Only transition attempts count towards the error-rate denominator
+        match event.event_type {
+            ExecutionModeEventType::ModeChanged => state.transitions.record(event.timestamp),
+            ExecutionModeEventType::SafetyCheckFailed |
+            ExecutionModeEventType::ValidationFailed |
+            ExecutionModeEventType::TransitionRateLimited => {
+                state.transitions.record(event.timestamp);
+                state.failed_transitions.record(event.timestamp);
+            }
+            ExecutionModeEventType::EmergencyStopTriggered => state.emergency_stops.record(event.timestamp),
+            _ => {}
+        }
+        
+        let transitions = state.transitions.count(now);
+        let failed = state.failed_transitions.count(now);
+        let stops = state.emergency_stops.count(now);
+        let error_rate = if transitions > 0 { failed as f64 / transitions as f64 } else { 0.0 };
+        
+        let mut exceeded = Vec::new();
+        if failed > thresholds.max_failed_transitions_per_hour as usize {
+            exceeded.push((ThresholdKind::FailedTransitions, failed as f64));
+        }
+        if stops > thresholds.max_emergency_stops_per_hour as usize {
+            exceeded.push((ThresholdKind::EmergencyStops, stops as f64));
+        }
+        // A handful of transitions says nothing about the rate: one failure
+        // out of one would otherwise read as 100%
+        if transitions >= thresholds.min_transitions_for_error_rate as usize && error_rate > thresholds.max_error_rate {
+            exceeded.push((ThresholdKind::ErrorRate, error_rate));
+        }
+        
+        exceeded.retain(|(kind, _)| {
+            let cooling = state.last_alerted.get(kind).is_some_and(|last| now < *last + cooldown);
+            if !cooling {
+                state.last_alerted.insert(*kind, now);
+            }
+            !cooling
+        });
+        exceeded
+    }
+
     /// Check alert conditions for guard execution
     /// التحقق من شروط التنبيه لتنفيذ الحارس
     async fn check_guard_execution_alerts(&self, record: &GuardExecutionRecord) -> MonitorResult<()> {
@@ -719,6 +1013,25 @@ impl ExecutionModeMonitor {
             ).await?;
         }
         
+        // Check computed operation risk against the configured ceiling
+        let computed_risk = record.guard_results.iter()
+            .map(|r| RiskLevel::from_score(r.risk_assessment.risk_score))
+            .chain(std::iter::once(record.operation.risk_level))
+            .max()
+            .unwrap_or(RiskLevel::None);
+        if computed_risk.exceeds(&thresholds.max_risk_level) {
+            let mut data = HashMap::new();
+            data.insert("operation_id".to_string(), serde_json::Value::String(record.operation.id.clone()));
+            data.insert("risk_level".to_string(), serde_json::json!(computed_risk));
+            self.create_alert(
+                AlertType::HighRiskOperation,
+                AlertSeverity::High,
+                format!("Operation risk {:?} exceeds maximum {:?}", computed_risk, thresholds.max_risk_level),
+                "safety_guards".to_string(),
+                data,
+            ).await?;
+        }
+        
         // Check for slow execution
         if record.total_execution_time_ms > thresholds.max_response_time_ms {
             self.create_alert(
@@ -775,7 +1088,7 @@ impl ExecutionModeMonitor {
         // Send alert notifications
         self.send_alert_notifications(&alert).await?;
         
-        warn!("Alert created: {} - {}", alert.alert_type, alert.message);
+        warn!("Alert created: {:?} - {}", alert.alert_type, alert.message);
         Ok(())
     }
 
@@ -798,8 +1111,8 @@ impl ExecutionModeMonitor {
         
         // Send webhook alerts
         if config.enable_webhook_alerts && !config.webhook_urls.is_empty() {
-            // In a real implementation, this would send actual webhooks
-            debug!("Sending webhook alert to {} URLs", config.webhook_urls.len());
+            // Delivery runs in the background; failures land in delivery_failures
+            let _ = self.alert_manager.dispatch(alert.clone()).await;
         }
         
         Ok(())
@@ -808,22 +1121,67 @@ impl ExecutionModeMonitor {
     /// Clean up old metrics
     /// تنظيف المقاييس القديمة
     pub async fn cleanup_old_metrics(&self) -> MonitorResult<()> {
+        self.prune_now().await;
+        Ok(())
+    }
+
+    /// Drop everything older than `metrics_retention_hours`
+    /// حذف كل ما هو أقدم من فترة الاحتفاظ
+    pub async fn prune_now(&self) -> PruneSummary {
         let cutoff_time = Utc::now() - chrono::Duration::hours(self.config.metrics_retention_hours as i64);
+        let mut summary = PruneSummary::default();
         
         // Clean up old events
         {
             let mut events = self.event_history.write().await;
+            let before = events.len();
             events.retain(|e| e.timestamp > cutoff_time);
+            summary.events_removed = before - events.len();
         }
         
         // Clean up old alerts
         {
             let mut alerts = self.alert_manager.alert_history.write().await;
+            let before = alerts.len();
             alerts.retain(|a| a.timestamp > cutoff_time);
+            summary.alerts_removed = before - alerts.len();
         }
         
-        info!("Cleaned up metrics older than {} hours", self.config.metrics_retention_hours);
-        Ok(())
+        // Counters that haven't moved within the window describe a stale period
+        {
+            let mut metrics = self.metrics.write().await;
+            if metrics.last_updated <= cutoff_time {
+                *metrics = ExecutionModeMetrics::default();
+                summary.metrics_reset = true;
+            }
+        }
+        
+        debug!(
+            "Pruned {} events and {} alerts older than {} hours",
+            summary.events_removed, summary.alerts_removed, self.config.metrics_retention_hours
+        );
+        summary
+    }
+
+    /// Spawn a task that prunes on `interval` until the monitor is dropped
+    /// تشغيل مهمة دورية للتقليم حتى يتم إسقاط المراقب
+    pub fn start_retention_task(self: &Arc<Self>, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        let monitor = Arc::downgrade(self);
+        
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            
+            loop {
+                ticker.tick().await;
+                match monitor.upgrade() {
+                    Some(monitor) => {
+                        monitor.prune_now().await;
+                    }
+                    None => break,
+                }
+            }
+        })
     }
 
     /// Generate monitoring report
@@ -926,6 +1284,8 @@ impl Default for ExecutionModeMetrics {
             avg_response_time_ms: 0.0,
             error_rate: 0.0,
             risk_level_distribution: HashMap::new(),
+            real_operations: 0,
+            simulated_operations: 0,
             performance_metrics: PerformanceMetrics::default(),
             security_metrics: SecurityMetrics::default(),
             last_updated: Utc::now(),
@@ -954,6 +1314,7 @@ impl Default for SecurityMetrics {
             mfa_challenges: 0,
             suspicious_operations: 0,
             blocked_operations: 0,
+            emergency_stop_blocks: 0,
             security_violations: 0,
             audit_log_entries: 0,
         }
@@ -968,10 +1329,90 @@ impl AlertManager {
             config,
             active_alerts: Arc::new(RwLock::new(Vec::new())),
             alert_history: Arc::new(RwLock::new(Vec::new())),
+            last_dispatched: Arc::new(RwLock::new(HashMap::new())),
+            delivery_failures: Arc::new(RwLock::new(VecDeque::new())),
+            http_client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// POST the alert as JSON to every configured webhook on a background
+    /// task, so callers never wait on HTTP. Returns `None` when the alert
+    /// type is still cooling down and nothing was sent; otherwise the handle
+    /// of the delivery task. Failed deliveries are recorded, never propagated.
+    /// إرسال التنبيه كـ JSON إلى كل webhook في مهمة خلفية مع احترام فترة التبريد لكل نوع
+    pub async fn dispatch(&self, alert: Alert) -> Option<tokio::task::JoinHandle<()>> {
+        if self.config.webhook_urls.is_empty() {
+            return None;
+        }
+        
+        {
+            let mut last_dispatched = self.last_dispatched.write().await;
+            let cooldown = chrono::Duration::minutes(self.config.alert_cooldown_minutes as i64);
+            if let Some(last) = last_dispatched.get(&alert.alert_type) {
+                if alert.timestamp < *last + cooldown {
+                    debug!("Alert {:?} suppressed by cooldown", alert.alert_type);
+                    return None;
+                }
+            }
+            last_dispatched.insert(alert.alert_type.clone(), alert.timestamp);
         }
+        
+        let urls = self.config.webhook_urls.clone();
+        let client = self.http_client.clone();
+        let delivery_failures = self.delivery_failures.clone();
+        Some(tokio::spawn(async move {
+            for url in urls {
+                let result = client
+                    .post(&url)
+                    .json(&alert)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status());
+                
+                if let Err(e) = result {
+                    error!("Webhook delivery to {} failed for alert {}: {}", url, alert.id, e);
+                    let mut failures = delivery_failures.write().await;
+                    failures.push_back(DeliveryFailure {
+                        alert_id: alert.id.clone(),
+                        url,
+                        error: e.to_string(),
+                        timestamp: Utc::now(),
+                    });
+                    while failures.len() > MAX_DELIVERY_FAILURES {
+                        failures.pop_front();
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Get recorded webhook delivery failures
+    /// الحصول على عمليات تسليم webhook الفاشلة المسجلة
+    pub async fn get_delivery_failures(&self) -> Vec<DeliveryFailure> {
+        self.delivery_failures.read().await.iter().cloned().collect()
     }
 }
 
+/// Prune Summary
+/// ملخص التقليم
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneSummary {
+    /// Events removed from history
+    /// الأحداث المحذوفة من السجل
+    pub events_removed: usize,
+    
+    /// Alerts removed from history
+    /// التنبيهات المحذوفة من السجل
+    pub alerts_removed: usize,
+    
+    /// Metrics were reset because they were last updated before the window
+    /// تمت إعادة تعيين المقاييس لأنها أقدم من النافذة
+    pub metrics_reset: bool,
+}
+
 /// Monitoring Report
 /// تقرير المراقبة
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1123,5 +1564,389 @@ mod tests {
         assert_eq!(thresholds.max_failed_transitions_per_hour, 5);
         assert_eq!(thresholds.max_emergency_stops_per_hour, 2);
         assert_eq!(thresholds.max_risk_level, RiskLevel::High);
+        assert_eq!(thresholds.min_transitions_for_error_rate, 20);
+    }
+
+    /// Accepts connections forever, counting requests and keeping their bodies
+    async fn spawn_webhook_server() -> (String, Arc<RwLock<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        let bodies = Arc::new(RwLock::new(Vec::new()));
+        let received = bodies.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 64 * 1024];
+                let mut read = 0;
+                loop {
+                    let n = socket.read(&mut buf[read..]).await.unwrap();
+                    read += n;
+                    let text = String::from_utf8_lossy(&buf[..read]).to_string();
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text[..end].lines()
+                            .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length: ").map(|v| v.trim().parse::<usize>().unwrap()))
+                            .unwrap_or(0);
+                        if read >= end + 4 + length || n == 0 {
+                            received.write().await.push(text[end + 4..].to_string());
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await
+                    .unwrap();
+            }
+        });
+
+        (url, bodies)
+    }
+
+    fn emergency_alert() -> Alert {
+        Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            alert_type: AlertType::EmergencyStopTriggered,
+            severity: AlertSeverity::Critical,
+            message: "Emergency stop triggered".to_string(),
+            timestamp: Utc::now(),
+            source: "test".to_string(),
+            data: HashMap::new(),
+            acknowledged: false,
+            resolved: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dispatch_and_cooldown() {
+        let (url, bodies) = spawn_webhook_server().await;
+        let manager = AlertManager::new(AlertConfig {
+            enable_webhook_alerts: true,
+            webhook_urls: vec![url],
+            ..AlertConfig::default()
+        });
+
+        let alert = emergency_alert();
+        manager.dispatch(alert.clone()).await.expect("first alert is sent").await.unwrap();
+        assert!(manager.dispatch(emergency_alert()).await.is_none());
+
+        // A different alert type has its own cooldown
+        let mut other = emergency_alert();
+        other.alert_type = AlertType::SecurityViolation;
+        manager.dispatch(other).await.expect("other type is sent").await.unwrap();
+
+        let bodies = bodies.read().await;
+        assert_eq!(bodies.len(), 2);
+        let delivered: Alert = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(delivered.id, alert.id);
+        assert!(manager.get_delivery_failures().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_failure_keeps_active_alert() {
+        // Bind then drop to get a port nothing is listening on
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let monitor = ExecutionModeMonitor::with_alert_config(
+            MonitorConfig::default(),
+            AlertConfig {
+                enable_webhook_alerts: true,
+                webhook_urls: vec![format!("http://{}/alerts", addr)],
+                ..AlertConfig::default()
+            },
+        );
+
+        monitor.create_alert(
+            AlertType::EmergencyStopTriggered,
+            AlertSeverity::Critical,
+            "Emergency stop triggered".to_string(),
+            "test".to_string(),
+            HashMap::new(),
+        ).await.unwrap();
+
+        // The alert is recorded before delivery has been attempted
+        assert_eq!(monitor.get_active_alerts().await.len(), 1);
+        let mut failures = Vec::new();
+        for _ in 0..100 {
+            failures = monitor.alert_manager().get_delivery_failures().await;
+            if !failures.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].url.ends_with("/alerts"));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_failures_are_capped() {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let manager = AlertManager::new(AlertConfig {
+            enable_webhook_alerts: true,
+            webhook_urls: vec![format!("http://{}/alerts", addr)],
+            alert_cooldown_minutes: 0,
+            ..AlertConfig::default()
+        });
+        {
+            let mut failures = manager.delivery_failures.write().await;
+            for i in 0..MAX_DELIVERY_FAILURES {
+                failures.push_back(DeliveryFailure {
+                    alert_id: format!("old-{}", i),
+                    url: "http://old".to_string(),
+                    error: "refused".to_string(),
+                    timestamp: Utc::now(),
+                });
+            }
+        }
+
+        let alert = emergency_alert();
+        manager.dispatch(alert.clone()).await.unwrap().await.unwrap();
+
+        let failures = manager.get_delivery_failures().await;
+        assert_eq!(failures.len(), MAX_DELIVERY_FAILURES);
+        assert_eq!(failures[0].alert_id, "old-1");
+        assert_eq!(failures.last().unwrap().alert_id, alert.id);
+    }
+
+    fn failed_transition_event(timestamp: DateTime<Utc>) -> ExecutionModeEvent {
+        ExecutionModeEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type: ExecutionModeEventType::SafetyCheckFailed,
+            mode: ExecutionMode::DryRun,
+            timestamp,
+            source: "test".to_string(),
+            data: HashMap::new(),
+        }
+    }
+
+    async fn transition_failure_alerts(monitor: &ExecutionModeMonitor) -> usize {
+        monitor.get_alert_history(None).await.iter()
+            .filter(|a| a.alert_type == AlertType::ModeTransitionFailure)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_failed_transition_threshold_alerts_once() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        let max = AlertThresholds::default().max_failed_transitions_per_hour as usize;
+
+        for _ in 0..max {
+            monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        }
+        assert_eq!(transition_failure_alerts(&monitor).await, 0);
+
+        // Crossing the threshold raises one alert, further failures stay quiet
+        for _ in 0..3 {
+            monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        }
+        assert_eq!(transition_failure_alerts(&monitor).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_transition_threshold_after_cooldown() {
+        let monitor = ExecutionModeMonitor::with_alert_config(
+            MonitorConfig::default(),
+            AlertConfig { alert_cooldown_minutes: 0, ..AlertConfig::default() },
+        );
+        let max = AlertThresholds::default().max_failed_transitions_per_hour as usize;
+
+        for _ in 0..=max {
+            monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        }
+        assert_eq!(transition_failure_alerts(&monitor).await, 1);
+
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        assert_eq!(transition_failure_alerts(&monitor).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_transition_rate_limit_raises_alert() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        let mut event = failed_transition_event(Utc::now());
+        event.event_type = ExecutionModeEventType::TransitionRateLimited;
+
+        monitor.record_event(event).await.unwrap();
+
+        let alerts = monitor.get_alert_history(None).await;
+        assert!(alerts.iter().any(|a| a.alert_type == AlertType::Custom("transition_rate".to_string())));
+        assert_eq!(monitor.get_metrics().await.failed_transitions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failures_outside_window_are_ignored() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        let max = AlertThresholds::default().max_failed_transitions_per_hour as usize;
+        let two_hours_ago = Utc::now() - chrono::Duration::hours(2);
+
+        for _ in 0..max * 2 {
+            monitor.record_event(failed_transition_event(two_hours_ago)).await.unwrap();
+        }
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        assert_eq!(transition_failure_alerts(&monitor).await, 0);
+    }
+
+    async fn error_rate_alerts(monitor: &ExecutionModeMonitor) -> usize {
+        monitor.get_alert_history(None).await.iter()
+            .filter(|a| a.alert_type == AlertType::Custom("error_rate".to_string()))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_single_failure_does_not_trip_error_rate() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        assert_eq!(error_rate_alerts(&monitor).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_alerts_once_sample_is_large_enough() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        let min = AlertThresholds::default().min_transitions_for_error_rate as usize;
+
+        // 2 failures are under the failed-transition cap but 10% of 20
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        for _ in 2..min - 1 {
+            monitor.record_event(event_at(Utc::now())).await.unwrap();
+        }
+        assert_eq!(error_rate_alerts(&monitor).await, 0);
+
+        monitor.record_event(event_at(Utc::now())).await.unwrap();
+        assert_eq!(error_rate_alerts(&monitor).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_non_transition_events_do_not_dilute_error_rate() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        let min = AlertThresholds::default().min_transitions_for_error_rate as usize;
+
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        monitor.record_event(failed_transition_event(Utc::now())).await.unwrap();
+        for _ in 0..min {
+            let mut event = event_at(Utc::now());
+            event.event_type = ExecutionModeEventType::ConfigurationUpdated;
+            monitor.record_event(event).await.unwrap();
+        }
+        // 2 attempts so far: too few to judge a rate, however many other events
+        assert_eq!(error_rate_alerts(&monitor).await, 0);
+
+        for _ in 2..min {
+            monitor.record_event(event_at(Utc::now())).await.unwrap();
+        }
+        assert_eq!(error_rate_alerts(&monitor).await, 1);
+    }
+
+    fn event_at(timestamp: DateTime<Utc>) -> ExecutionModeEvent {
+        ExecutionModeEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type: ExecutionModeEventType::ModeChanged,
+            mode: ExecutionMode::DryRun,
+            timestamp,
+            source: "test".to_string(),
+            data: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_now_removes_old_events() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        let old = Utc::now() - chrono::Duration::hours(25);
+
+        monitor.record_event(event_at(old)).await.unwrap();
+        monitor.record_event(event_at(old)).await.unwrap();
+        monitor.record_event(event_at(Utc::now())).await.unwrap();
+        assert_eq!(monitor.get_event_history(None).await.len(), 3);
+
+        let summary = monitor.prune_now().await;
+        assert_eq!(summary.events_removed, 2);
+        assert!(!summary.metrics_reset);
+
+        let remaining = monitor.get_event_history(None).await;
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].timestamp > old);
+    }
+
+    #[tokio::test]
+    async fn test_prune_now_resets_stale_metrics() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+        monitor.record_event(event_at(Utc::now())).await.unwrap();
+        monitor.metrics.write().await.last_updated = Utc::now() - chrono::Duration::hours(48);
+
+        assert!(monitor.prune_now().await.metrics_reset);
+        assert_eq!(monitor.get_metrics().await.total_transitions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_retention_task_stops_with_monitor() {
+        let monitor = Arc::new(ExecutionModeMonitor::new(MonitorConfig::default()));
+        let handle = monitor.start_retention_task(std::time::Duration::from_millis(10));
+        drop(monitor);
+        tokio::time::timeout(std::time::Duration::from_secs(1), handle).await.unwrap().unwrap();
+    }
+
+    fn guard_record(risk_level: RiskLevel, risk_score: f64) -> GuardExecutionRecord {
+        use super::super::safety_guards::{GuardDecision, GuardResult, Operation, OperationType, RiskAssessment};
+
+        GuardExecutionRecord {
+            id: "record".to_string(),
+            operation: Operation {
+                id: "op".to_string(),
+                operation_type: OperationType::Trade,
+                user: "trader".to_string(),
+                timestamp: Utc::now(),
+                parameters: HashMap::new(),
+                risk_level,
+                metadata: HashMap::new(),
+            },
+            mode: ExecutionMode::Live,
+            guard_results: vec![GuardResult {
+                guard_name: "test_guard".to_string(),
+                allowed: true,
+                reason: "ok".to_string(),
+                risk_assessment: RiskAssessment {
+                    risk_score,
+                    risk_factors: vec![],
+                    mitigation_suggestions: vec![],
+                    confidence_level: 1.0,
+                },
+                recommendations: vec![],
+                execution_time_us: 1,
+            }],
+            overall_decision: GuardDecision::Allow,
+            timestamp: Utc::now(),
+            total_execution_time_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alert_when_risk_exceeds_max_risk_level() {
+        let mut config = MonitorConfig::default();
+        config.alert_thresholds.max_risk_level = RiskLevel::Medium;
+        let monitor = ExecutionModeMonitor::new(config);
+
+        monitor.record_guard_execution(guard_record(RiskLevel::Low, 0.5)).await.unwrap();
+        assert!(monitor.get_active_alerts().await.is_empty());
+
+        // Computed from the guard's risk score even when the operation is tagged Low
+        monitor.record_guard_execution(guard_record(RiskLevel::Low, 0.9)).await.unwrap();
+        let alerts = monitor.get_active_alerts().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].alert_type, AlertType::HighRiskOperation);
+    }
+    #[tokio::test]
+    async fn test_critical_risk_alerts_with_default_config() {
+        let monitor = ExecutionModeMonitor::new(MonitorConfig::default());
+
+        monitor.record_guard_execution(guard_record(RiskLevel::High, 0.9)).await.unwrap();
+        assert!(monitor.get_active_alerts().await.is_empty());
+
+        monitor.record_guard_execution(guard_record(RiskLevel::Low, 0.97)).await.unwrap();
+        let alerts = monitor.get_active_alerts().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].alert_type, AlertType::HighRiskOperation);
     }
 }
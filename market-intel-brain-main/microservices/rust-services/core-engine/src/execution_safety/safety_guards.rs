@@ -3,6 +3,7 @@
 // حراسات السلامة لأنماط التنفيذ - المهمة 21.5 ب
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::HashMap;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
@@ -13,6 +14,15 @@ use thiserror::Error;
 use super::execution_mode::{
     ExecutionMode, ExecutionModeResult, ExecutionModeError, ExecutionContext, RiskLevel
 };
+use super::monitoring::ExecutionModeMonitor;
+
+/// Priority of guards that must run before every other guard
+/// أولوية الحراس التي يجب تشغيلها قبل أي حارس آخر
+pub const KILL_SWITCH_PRIORITY: u32 = 0;
+
+/// Priority assigned to guards that don't override `SafetyGuard::priority`
+/// الأولوية الافتراضية للحراس
+pub const DEFAULT_GUARD_PRIORITY: u32 = 100;
 
 /// Safety Guard Trait
 /// واجهة حارس السلامة
@@ -36,6 +46,12 @@ pub trait SafetyGuard: Send + Sync {
     /// Get the risk level threshold for this guard
     /// الحصول على عتبة مستوى المخاطر لهذا الحارس
     fn risk_threshold(&self) -> RiskLevel;
+    
+    /// Execution priority, lower runs first
+    /// أولوية التنفيذ، الأقل يعمل أولاً
+    fn priority(&self) -> u32 {
+        DEFAULT_GUARD_PRIORITY
+    }
 }
 
 /// Operation Type
@@ -213,7 +229,7 @@ pub enum RiskCategory {
 pub struct SafetyGuardManager {
     /// Registered guards
     /// الحراس المسجلون
-    guards: Arc<RwLock<HashMap<String, Box<dyn SafetyGuard>>>>,
+    guards: Arc<RwLock<HashMap<String, Arc<dyn SafetyGuard>>>>,
     
     /// Guard execution history
     /// سجل تنفيذ الحراس
@@ -222,6 +238,10 @@ pub struct SafetyGuardManager {
     /// Configuration
     /// التكوين
     config: GuardManagerConfig,
+    
+    /// Monitor receiving every execution record
+    /// المراقب الذي يستقبل كل سجل تنفيذ
+    monitor: Option<Arc<ExecutionModeMonitor>>,
 }
 
 /// Guard Manager Configuration
@@ -306,7 +326,7 @@ pub struct GuardExecutionRecord {
 
 /// Guard Decision
 /// قرار الحارس
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GuardDecision {
     /// Allow operation
     /// السماح بالعملية
@@ -325,6 +345,23 @@ pub enum GuardDecision {
     RequireManualReview,
 }
 
+/// Outcome of [`SafetyGuardManager::execute`]
+/// نتيجة تنفيذ عملية عبر مدير الحراس
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionOutcome<T> {
+    /// Guards allowed it and the executor ran
+    /// سمح الحراس وتم التنفيذ
+    Executed(T),
+    
+    /// Guards allowed it but the mode doesn't execute; the intent was logged
+    /// سمح الحراس لكن النمط لا ينفذ؛ تم تسجيل النية
+    Simulated,
+    
+    /// Guards did not allow it
+    /// لم يسمح الحراس بالعملية
+    Blocked(GuardDecision),
+}
+
 /// Safety Guard Error
 /// خطأ حارس السلامة
 #[derive(Error, Debug)]
@@ -360,9 +397,17 @@ impl SafetyGuardManager {
             guards: Arc::new(RwLock::new(HashMap::new())),
             execution_history: Arc::new(RwLock::new(Vec::new())),
             config,
+            monitor: None,
         }
     }
 
+    /// Forward execution records to a monitor
+    /// إرسال سجلات التنفيذ إلى مراقب
+    pub fn with_monitor(mut self, monitor: Arc<ExecutionModeMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
     /// Register a safety guard
     /// تسجيل حارس سلامة
     pub async fn register_guard(&self, guard: Box<dyn SafetyGuard>) -> SafetyGuardResult<()> {
@@ -371,7 +416,7 @@ impl SafetyGuardManager {
         
         {
             let mut guards = self.guards.write().await;
-            guards.insert(name, guard);
+            guards.insert(name, Arc::from(guard));
         }
         
         Ok(())
@@ -412,16 +457,28 @@ impl SafetyGuardManager {
             return Ok(self.create_empty_record(operation, mode, start_time.elapsed().as_millis() as u64));
         }
         
-        // Execute guards
-        let guard_results = if self.config.enable_parallel_execution {
-            self.execute_guards_parallel(&applicable_guards, &operation, mode).await?
+        // Kill-switch guards run first and short-circuit everything else
+        let split = applicable_guards.iter()
+            .position(|g| g.priority() > KILL_SWITCH_PRIORITY)
+            .unwrap_or(applicable_guards.len());
+        let (critical_guards, other_guards) = applicable_guards.split_at(split);
+        let mut guard_results = self.execute_guards_sequential(critical_guards, &operation, mode).await?;
+        
+        let overall_decision = if guard_results.iter().any(|r| !r.allowed) {
+            GuardDecision::Deny
         } else {
-            self.execute_guards_sequential(&applicable_guards, &operation, mode).await?
+            // Execute guards
+            let results = if self.config.enable_parallel_execution {
+                self.execute_guards_parallel(other_guards, &operation, mode).await?
+            } else {
+                self.execute_guards_sequential(other_guards, &operation, mode).await?
+            };
+            guard_results.extend(results);
+            
+            // Make overall decision
+            self.make_decision(&guard_results, &operation, mode).await?
         };
         
-        // Make overall decision
-        let overall_decision = self.make_decision(&guard_results, &operation, mode).await?;
-        
         // Create execution record
         let record = GuardExecutionRecord {
             id: uuid::Uuid::new_v4().to_string(),
@@ -436,10 +493,62 @@ impl SafetyGuardManager {
         // Store execution record
         self.store_execution_record(record.clone()).await;
         
+        if let Some(monitor) = &self.monitor {
+            if let Err(e) = monitor.record_guard_execution(record.clone()).await {
+                warn!("Failed to record guard execution with monitor: {}", e);
+            }
+        }
+        
         info!("Operation check completed: {:?}", overall_decision);
         Ok(record)
     }
 
+    /// Check `operation` and, when allowed, run `executor`. Guards are evaluated
+    /// in every mode; DryRun logs the intended action and records it with the
+    /// monitor instead of running the executor.
+    /// التحقق من العملية ثم تنفيذها إذا سُمح بها؛ نمط التشغيل التجريبي يسجل النية فقط
+    pub async fn execute<T, F, Fut>(
+        &self,
+        operation: Operation,
+        mode: ExecutionMode,
+        executor: F,
+    ) -> SafetyGuardResult<ExecutionOutcome<T>>
+    where
+        F: FnOnce(Operation) -> Fut,
+        Fut: std::future::Future<Output = T>,
+    {
+        let record = self.check_operation(operation, mode).await?;
+        if record.overall_decision != GuardDecision::Allow {
+            return Ok(ExecutionOutcome::Blocked(record.overall_decision));
+        }
+        
+        let operation = record.operation;
+        match mode {
+            ExecutionMode::DryRun => {
+                info!(
+                    "[{}] Would execute {:?} {} for {}: {:?}",
+                    mode, operation.operation_type, operation.id, operation.user, operation.parameters
+                );
+                if let Some(monitor) = &self.monitor {
+                    monitor.record_simulated_operation(&operation, mode).await;
+                }
+                return Ok(ExecutionOutcome::Simulated);
+            }
+            // Never run anything under the kill switch, even without an EmergencyStopGuard
+            ExecutionMode::EmergencyStop => return Ok(ExecutionOutcome::Blocked(GuardDecision::Deny)),
+            ExecutionMode::Live | ExecutionMode::Backtest => {}
+        }
+        
+        let recorded = operation.clone();
+        let result = executor(operation).await;
+        if mode.allows_real_execution() {
+            if let Some(monitor) = &self.monitor {
+                monitor.record_real_operation(&recorded, mode).await;
+            }
+        }
+        Ok(ExecutionOutcome::Executed(result))
+    }
+
     /// Get execution history
     /// الحصول على سجل التنفيذ
     pub async fn get_execution_history(&self, limit: Option<usize>) -> Vec<GuardExecutionRecord> {
@@ -484,16 +593,17 @@ impl SafetyGuardManager {
 
     /// Get applicable guards for a mode
     /// الحصول على الحراس المطبقين لنمط
-    async fn get_applicable_guards(&self, mode: ExecutionMode) -> SafetyGuardResult<Vec<Arc<Box<dyn SafetyGuard>>>> {
+    async fn get_applicable_guards(&self, mode: ExecutionMode) -> SafetyGuardResult<Vec<Arc<dyn SafetyGuard>>> {
         let guards = self.guards.read().await;
         let mut applicable_guards = Vec::new();
         
         for guard in guards.values() {
             if guard.applies_to_modes().contains(&mode) {
-                applicable_guards.push(Arc::new(guard.clone()));
+                applicable_guards.push(guard.clone());
             }
         }
         
+        applicable_guards.sort_by_key(|g| g.priority());
         Ok(applicable_guards)
     }
 
@@ -501,7 +611,7 @@ impl SafetyGuardManager {
     /// تنفيذ الحراس بشكل متوازٍ
     async fn execute_guards_parallel(
         &self,
-        guards: &[Arc<Box<dyn SafetyGuard>>],
+        guards: &[Arc<dyn SafetyGuard>],
         operation: &Operation,
         mode: ExecutionMode,
     ) -> SafetyGuardResult<Vec<GuardResult>> {
@@ -509,6 +619,7 @@ impl SafetyGuardManager {
         let mut tasks = Vec::new();
         
         for guard in guards {
+            let guard = guard.clone();
             let semaphore = semaphore.clone();
             let operation = operation.clone();
             let mode = mode;
@@ -552,7 +663,7 @@ impl SafetyGuardManager {
     /// تنفيذ الحراس بشكل تسلسلي
     async fn execute_guards_sequential(
         &self,
-        guards: &[Arc<Box<dyn SafetyGuard>>],
+        guards: &[Arc<dyn SafetyGuard>],
         operation: &Operation,
         mode: ExecutionMode,
     ) -> SafetyGuardResult<Vec<GuardResult>> {
@@ -754,7 +865,7 @@ impl SafetyGuard for LiveTradingGuard {
     fn check(&self, mode: ExecutionMode, operation: &Operation) -> GuardResult {
         let start = std::time::Instant::now();
         
-        let allowed = match (mode, operation.operation_type) {
+        let allowed = match (mode, &operation.operation_type) {
             (ExecutionMode::Live, OperationType::Trade) => {
                 // Additional checks for live trading
                 operation.risk_level.value() <= RiskLevel::Medium.value()
@@ -762,6 +873,7 @@ impl SafetyGuard for LiveTradingGuard {
             (ExecutionMode::Live, _) => true, // Other operations allowed in live mode
             (ExecutionMode::DryRun, _) => true, // All operations allowed in dry run
             (ExecutionMode::Backtest, _) => true, // All operations allowed in backtest
+            (ExecutionMode::EmergencyStop, _) => false, // Nothing passes the kill switch
         };
         
         let reason = if allowed {
@@ -839,6 +951,7 @@ impl SafetyGuard for RiskLevelGuard {
             ExecutionMode::Live => RiskLevel::Medium,
             ExecutionMode::DryRun => RiskLevel::High,
             ExecutionMode::Backtest => RiskLevel::High,
+            ExecutionMode::EmergencyStop => RiskLevel::None,
         };
         
         let allowed = operation.risk_level.value() <= max_allowed_risk.value();
@@ -916,7 +1029,7 @@ impl SafetyGuard for ConfigurationGuard {
     fn check(&self, mode: ExecutionMode, operation: &Operation) -> GuardResult {
         let start = std::time::Instant::now();
         
-        let allowed = match (mode, operation.operation_type) {
+        let allowed = match (mode, &operation.operation_type) {
             (ExecutionMode::Live, OperationType::ConfigurationChange) => {
                 // Configuration changes in live mode require additional validation
                 operation.parameters.get("validated").map_or(false, |v| v.as_bool().unwrap_or(false))
@@ -969,6 +1082,270 @@ impl SafetyGuard for ConfigurationGuard {
     }
 }
 
+/// Emergency Stop Guard
+/// حارس التوقف الطارئ
+///
+/// Blocks every order-executing operation while the safety manager's kill
+/// switch is engaged or the checked mode is `EmergencyStop`.
+/// يحظر كل عمليات تنفيذ الأوامر أثناء تفعيل مفتاح الإيقاف الطارئ.
+pub struct EmergencyStopGuard {
+    name: String,
+    description: String,
+    kill_switch: Arc<AtomicBool>,
+}
+
+/// Name under which the emergency stop guard registers
+/// الاسم الذي يسجل به حارس التوقف الطارئ
+pub const EMERGENCY_STOP_GUARD_NAME: &str = "emergency_stop_guard";
+
+impl EmergencyStopGuard {
+    /// Create a guard bound to a safety manager's kill switch
+    /// (see `GlobalExecutionSafetyManager::kill_switch`)
+    /// إنشاء حارس مرتبط بمفتاح الإيقاف لمدير السلامة
+    pub fn new(kill_switch: Arc<AtomicBool>) -> Self {
+        Self {
+            name: EMERGENCY_STOP_GUARD_NAME.to_string(),
+            description: "Blocks all order execution while emergency stop is active".to_string(),
+            kill_switch,
+        }
+    }
+
+    fn executes_orders(operation_type: &OperationType) -> bool {
+        matches!(
+            operation_type,
+            OperationType::Trade | OperationType::OrderPlacement | OperationType::PositionManagement
+        )
+    }
+}
+
+impl SafetyGuard for EmergencyStopGuard {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn check(&self, mode: ExecutionMode, operation: &Operation) -> GuardResult {
+        let start = std::time::Instant::now();
+        
+        let stopped = mode.is_emergency_stop() || self.kill_switch.load(Ordering::SeqCst);
+        let allowed = !(stopped && Self::executes_orders(&operation.operation_type));
+        
+        let reason = if allowed {
+            "Emergency stop not active".to_string()
+        } else {
+            format!("Emergency stop active - {:?} blocked", operation.operation_type)
+        };
+        
+        GuardResult {
+            guard_name: self.name.clone(),
+            allowed,
+            reason,
+            risk_assessment: RiskAssessment {
+                risk_score: if allowed { 0.0 } else { 1.0 },
+                risk_factors: vec![],
+                mitigation_suggestions: if !allowed {
+                    vec!["Resume trading through DryRun once the incident is cleared".to_string()]
+                } else {
+                    vec![]
+                },
+                confidence_level: 1.0,
+            },
+            recommendations: vec![],
+            execution_time_us: start.elapsed().as_micros() as u64,
+        }
+    }
+
+    fn applies_to_modes(&self) -> Vec<ExecutionMode> {
+        vec![
+            ExecutionMode::Live,
+            ExecutionMode::DryRun,
+            ExecutionMode::Backtest,
+            ExecutionMode::EmergencyStop,
+        ]
+    }
+
+    fn risk_threshold(&self) -> RiskLevel {
+        RiskLevel::None
+    }
+
+    fn priority(&self) -> u32 {
+        KILL_SWITCH_PRIORITY
+    }
+}
+
+/// Position Tracker
+/// متتبع المراكز
+///
+/// Shared record of current absolute notional exposure per symbol.
+/// سجل مشترك للتعرض الاسمي الحالي لكل رمز.
+#[derive(Debug, Default)]
+pub struct PositionTracker {
+    exposures: parking_lot::RwLock<HashMap<String, f64>>,
+}
+
+impl PositionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current notional exposure for a symbol
+    /// التعرض الاسمي الحالي لرمز
+    pub fn exposure(&self, symbol: &str) -> f64 {
+        self.exposures.read().get(symbol).copied().unwrap_or(0.0)
+    }
+
+    /// Overwrite the exposure for a symbol
+    /// استبدال التعرض لرمز
+    pub fn set_exposure(&self, symbol: &str, notional: f64) {
+        self.exposures.write().insert(symbol.to_string(), notional);
+    }
+
+    /// Add a fill's notional to the symbol's exposure
+    /// إضافة القيمة الاسمية للتنفيذ إلى تعرض الرمز
+    pub fn record_fill(&self, symbol: &str, notional: f64) {
+        *self.exposures.write().entry(symbol.to_string()).or_insert(0.0) += notional;
+    }
+}
+
+/// Max Notional Guard
+/// حارس الحد الأقصى للقيمة الاسمية
+///
+/// Rejects trades whose notional (`price * quantity`), added to the symbol's
+/// current exposure, would exceed the configured per-symbol limit.
+/// يرفض الصفقات التي تتجاوز الحد الاسمي المسموح لكل رمز.
+pub struct MaxNotionalGuard {
+    name: String,
+    description: String,
+    default_limit: f64,
+    symbol_limits: HashMap<String, f64>,
+    positions: Arc<PositionTracker>,
+}
+
+impl MaxNotionalGuard {
+    pub fn new(default_limit: f64, positions: Arc<PositionTracker>) -> Self {
+        Self {
+            name: "max_notional_guard".to_string(),
+            description: "Caps cumulative notional exposure per symbol".to_string(),
+            default_limit,
+            symbol_limits: HashMap::new(),
+            positions,
+        }
+    }
+
+    /// Override the limit for a single symbol
+    /// تجاوز الحد لرمز واحد
+    pub fn with_symbol_limit(mut self, symbol: impl Into<String>, limit: f64) -> Self {
+        self.symbol_limits.insert(symbol.into(), limit);
+        self
+    }
+
+    /// Limit applied to a symbol
+    /// الحد المطبق على رمز
+    pub fn limit_for(&self, symbol: &str) -> f64 {
+        self.symbol_limits.get(symbol).copied().unwrap_or(self.default_limit)
+    }
+
+    /// Validate an order operation, returning the projected exposure
+    /// التحقق من عملية الأمر وإرجاع التعرض المتوقع
+    pub fn evaluate(&self, operation: &Operation) -> ExecutionModeResult<f64> {
+        let symbol = operation.parameters.get("symbol")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ExecutionModeError::InvalidOrder("missing symbol".to_string()))?;
+        let price = Self::number_param(operation, "price")?;
+        let quantity = Self::number_param(operation, "quantity")?;
+
+        let projected = self.positions.exposure(symbol) + (price * quantity).abs();
+        let limit = self.limit_for(symbol);
+        if projected > limit {
+            return Err(ExecutionModeError::NotionalLimitExceeded {
+                symbol: symbol.to_string(),
+                notional: projected,
+                limit,
+            });
+        }
+
+        Ok(projected)
+    }
+
+    fn number_param(operation: &Operation, key: &str) -> ExecutionModeResult<f64> {
+        operation.parameters.get(key)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| ExecutionModeError::InvalidOrder(format!("missing or non-numeric {}", key)))
+    }
+}
+
+impl SafetyGuard for MaxNotionalGuard {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn check(&self, _mode: ExecutionMode, operation: &Operation) -> GuardResult {
+        let start = std::time::Instant::now();
+
+        let is_order = matches!(
+            operation.operation_type,
+            OperationType::Trade | OperationType::OrderPlacement
+        );
+
+        let (allowed, reason, utilization) = if !is_order {
+            (true, "Not an order operation".to_string(), 0.0)
+        } else {
+            match self.evaluate(operation) {
+                Ok(projected) => {
+                    let symbol = operation.parameters["symbol"].as_str().unwrap_or_default();
+                    let limit = self.limit_for(symbol);
+                    let utilization = if limit > 0.0 { projected / limit } else { 1.0 };
+                    (true, format!("Projected notional {:.2} within limit {:.2}", projected, limit), utilization)
+                }
+                Err(e) => (false, e.to_string(), 1.0),
+            }
+        };
+
+        let risk_assessment = RiskAssessment {
+            risk_score: utilization.clamp(0.0, 1.0),
+            risk_factors: vec![
+                RiskFactor {
+                    name: "notional_exposure".to_string(),
+                    description: "Share of the per-symbol notional limit in use".to_string(),
+                    impact: utilization.clamp(0.0, 1.0),
+                    category: RiskCategory::Market,
+                }
+            ],
+            mitigation_suggestions: if !allowed {
+                vec!["Reduce order quantity or close existing exposure".to_string()]
+            } else {
+                vec![]
+            },
+            confidence_level: 0.95,
+        };
+
+        GuardResult {
+            guard_name: self.name.clone(),
+            allowed,
+            reason,
+            risk_assessment,
+            recommendations: vec![],
+            execution_time_us: start.elapsed().as_micros() as u64,
+        }
+    }
+
+    fn applies_to_modes(&self) -> Vec<ExecutionMode> {
+        vec![ExecutionMode::Live, ExecutionMode::DryRun]
+    }
+
+    fn risk_threshold(&self) -> RiskLevel {
+        RiskLevel::None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1048,4 +1425,154 @@ mod tests {
         assert_eq!(format!("{:?}", GuardDecision::RequireApproval), "RequireApproval");
         assert_eq!(format!("{:?}", GuardDecision::RequireManualReview), "RequireManualReview");
     }
+
+    fn order(symbol: &str, price: f64, quantity: f64) -> Operation {
+        let mut parameters = HashMap::new();
+        parameters.insert("symbol".to_string(), serde_json::json!(symbol));
+        parameters.insert("price".to_string(), serde_json::json!(price));
+        parameters.insert("quantity".to_string(), serde_json::json!(quantity));
+        Operation {
+            id: "order-1".to_string(),
+            operation_type: OperationType::OrderPlacement,
+            user: "trader".to_string(),
+            timestamp: Utc::now(),
+            parameters,
+            risk_level: RiskLevel::Low,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_max_notional_guard_allows_order_under_limit() {
+        let positions = Arc::new(PositionTracker::new());
+        positions.set_exposure("AAPL", 40_000.0);
+        let guard = MaxNotionalGuard::new(100_000.0, positions);
+
+        let result = guard.check(ExecutionMode::Live, &order("AAPL", 150.0, 100.0));
+        assert!(result.allowed, "{}", result.reason);
+        assert_eq!(guard.evaluate(&order("AAPL", 150.0, 100.0)).unwrap(), 55_000.0);
+    }
+
+    #[test]
+    fn test_max_notional_guard_blocks_breach() {
+        let positions = Arc::new(PositionTracker::new());
+        positions.set_exposure("AAPL", 90_000.0);
+        let guard = MaxNotionalGuard::new(100_000.0, positions.clone());
+
+        let op = order("AAPL", 150.0, 100.0);
+        assert!(!guard.check(ExecutionMode::Live, &op).allowed);
+        match guard.evaluate(&op) {
+            Err(ExecutionModeError::NotionalLimitExceeded { symbol, notional, limit }) => {
+                assert_eq!(symbol, "AAPL");
+                assert_eq!(notional, 105_000.0);
+                assert_eq!(limit, 100_000.0);
+            }
+            other => panic!("expected NotionalLimitExceeded, got {:?}", other),
+        }
+
+        // Exposure on another symbol is tracked separately
+        assert!(guard.check(ExecutionMode::Live, &order("MSFT", 150.0, 100.0)).allowed);
+    }
+
+    #[test]
+    fn test_max_notional_guard_symbol_override_and_bad_params() {
+        let positions = Arc::new(PositionTracker::new());
+        let guard = MaxNotionalGuard::new(100_000.0, positions).with_symbol_limit("TSLA", 1_000.0);
+
+        assert!(!guard.check(ExecutionMode::Live, &order("TSLA", 200.0, 10.0)).allowed);
+
+        let mut op = order("AAPL", 1.0, 1.0);
+        op.parameters.remove("price");
+        assert!(matches!(guard.evaluate(&op), Err(ExecutionModeError::InvalidOrder(_))));
+    }
+
+    #[tokio::test]
+    async fn test_emergency_stop_blocks_all_execution() {
+        use crate::execution_safety::monitoring::MonitorConfig;
+        use crate::execution_safety::safety_manager::{GlobalExecutionSafetyManager, SafetyManagerConfig};
+
+        let safety_manager = GlobalExecutionSafetyManager::new(SafetyManagerConfig::default());
+        safety_manager.emergency_stop("test".to_string()).await.unwrap();
+        assert_eq!(safety_manager.current_mode().await, ExecutionMode::EmergencyStop);
+
+        let monitor = Arc::new(ExecutionModeMonitor::new(MonitorConfig::default()));
+        let manager = SafetyGuardManager::new(GuardManagerConfig::default()).with_monitor(monitor.clone());
+        manager.register_guard(Box::new(RiskLevelGuard::new())).await.unwrap();
+        manager.register_guard(Box::new(EmergencyStopGuard::new(safety_manager.kill_switch()))).await.unwrap();
+
+        let order_types = [OperationType::Trade, OperationType::OrderPlacement, OperationType::PositionManagement];
+        // The kill switch applies even when a caller passes a stale mode
+        for mode in [ExecutionMode::EmergencyStop, ExecutionMode::Live] {
+            for operation_type in order_types.iter().cloned() {
+                let mut op = order("AAPL", 1.0, 1.0);
+                op.operation_type = operation_type;
+                let record = manager.check_operation(op, mode).await.unwrap();
+                assert_eq!(record.overall_decision, GuardDecision::Deny);
+                assert_eq!(record.guard_results.len(), 1);
+                assert_eq!(record.guard_results[0].guard_name, EMERGENCY_STOP_GUARD_NAME);
+            }
+        }
+
+        let metrics = monitor.get_metrics().await;
+        assert_eq!(metrics.security_metrics.emergency_stop_blocks, 6);
+        assert_eq!(metrics.security_metrics.blocked_operations, 6);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_logs_intent_without_executing() {
+        use crate::execution_safety::monitoring::MonitorConfig;
+        use std::sync::atomic::AtomicUsize;
+
+        let monitor = Arc::new(ExecutionModeMonitor::new(MonitorConfig::default()));
+        let positions = Arc::new(PositionTracker::new());
+        let manager = SafetyGuardManager::new(GuardManagerConfig::default()).with_monitor(monitor.clone());
+        manager.register_guard(Box::new(MaxNotionalGuard::new(100_000.0, positions))).await.unwrap();
+
+        let executed = Arc::new(AtomicUsize::new(0));
+        let run = |op: Operation, mode: ExecutionMode| {
+            let executed = executed.clone();
+            manager.execute(op, mode, move |_| async move {
+                executed.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        // Allowed in DryRun: logged, not executed
+        let outcome = run(order("AAPL", 150.0, 100.0), ExecutionMode::DryRun).await.unwrap();
+        assert_eq!(outcome, ExecutionOutcome::Simulated);
+        assert_eq!(executed.load(Ordering::SeqCst), 0);
+
+        // Guards still apply in DryRun
+        let outcome = run(order("AAPL", 150.0, 10_000.0), ExecutionMode::DryRun).await.unwrap();
+        assert_eq!(outcome, ExecutionOutcome::Blocked(GuardDecision::Deny));
+
+        // Live reaches the executor
+        let outcome = run(order("AAPL", 150.0, 100.0), ExecutionMode::Live).await.unwrap();
+        assert_eq!(outcome, ExecutionOutcome::Executed(()));
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+
+        // So does Backtest, without counting as a real operation
+        let outcome = run(order("AAPL", 150.0, 100.0), ExecutionMode::Backtest).await.unwrap();
+        assert_eq!(outcome, ExecutionOutcome::Executed(()));
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+
+        let metrics = monitor.get_metrics().await;
+        assert_eq!(metrics.simulated_operations, 1);
+        assert_eq!(metrics.real_operations, 1);
+
+        let simulated = monitor.get_simulated_operations(None).await;
+        assert_eq!(simulated.len(), 1);
+        assert_eq!(simulated[0].mode, ExecutionMode::DryRun);
+        assert_eq!(simulated[0].operation.parameters["quantity"], serde_json::json!(100.0));
+    }
+
+    #[test]
+    fn test_emergency_stop_guard_idle() {
+        let guard = EmergencyStopGuard::new(Arc::new(AtomicBool::new(false)));
+        assert_eq!(guard.priority(), KILL_SWITCH_PRIORITY);
+        assert!(guard.check(ExecutionMode::Live, &order("AAPL", 1.0, 1.0)).allowed);
+
+        let mut op = order("AAPL", 1.0, 1.0);
+        op.operation_type = OperationType::DataAccess;
+        assert!(guard.check(ExecutionMode::EmergencyStop, &op).allowed);
+    }
 }
@@ -2,14 +2,17 @@
 // Global Execution Safety Manager - Phase 21.5 Task B
 // مدير السلامة العالمي للتنفيذ - المهمة 21.5 ب
 
-use std::sync::{Arc, RwLock};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::collections::HashMap;
-use tokio::sync::{Mutex, broadcast};
+use tokio::sync::{Mutex, RwLock, broadcast};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error, debug};
 use thiserror::Error;
 
+use super::audit::{ModeAuditEntry, ModeAuditLog};
+use super::monitoring::ExecutionModeMonitor;
 use super::execution_mode::{
     ExecutionMode, ExecutionModeResult, ExecutionModeError, ExecutionRequirements,
     ExecutionContext, RiskLevel, Environment, Permission, DataSourceRequirement, MonitoringRequirement
@@ -22,6 +25,10 @@ pub struct GlobalExecutionSafetyManager {
     /// نمط التنفيذ الحالي
     current_mode: Arc<RwLock<ExecutionMode>>,
     
+    /// Lock-free copy of `current_mode` for hot-path readers
+    /// نسخة بدون أقفال من النمط الحالي للقراء في المسار الساخن
+    mode_snapshot: Arc<AtomicU8>,
+    
     /// Execution requirements
     /// متطلبات التنفيذ
     requirements: Arc<RwLock<ExecutionRequirements>>,
@@ -38,6 +45,22 @@ pub struct GlobalExecutionSafetyManager {
     /// بث الأحداث لتغييرات النمط
     event_broadcaster: broadcast::Sender<ExecutionModeEvent>,
     
+    /// Kill switch flag, mirrors `current_mode == EmergencyStop` for sync readers
+    /// علامة مفتاح الإيقاف، تعكس حالة التوقف الطارئ للقراء المتزامنين
+    kill_switch: Arc<AtomicBool>,
+    
+    /// Optional limit on how often `set_mode` may change the mode
+    /// حد اختياري لعدد مرات تغيير النمط
+    transition_rate_guard: Option<Arc<TransitionRateGuard>>,
+    
+    /// Durable audit trail, present when audit logging is enabled and a path is configured
+    /// سجل المراجعة الدائم، موجود عند تمكين المراجعة وتحديد مسار
+    audit_log: Option<Arc<ModeAuditLog>>,
+    
+    /// Resolves approval tokens for Live; without one Live is never approved
+    /// يتحقق من رموز الموافقة للنمط الحي؛ بدونه لا تتم الموافقة على النمط الحي أبدًا
+    approval_verifier: Option<Arc<dyn ApprovalVerifier>>,
+    
     /// Configuration
     /// التكوين
     config: SafetyManagerConfig,
@@ -78,6 +101,11 @@ pub struct SafetyManagerConfig {
     /// Enable audit logging for all mode changes
     /// تمكين تسجيل المراجعة لجميع تغييرات النمط
     pub enable_audit_logging: bool,
+    
+    /// JSONL file receiving one line per mode transition
+    /// ملف JSONL يستقبل سطرًا لكل انتقال نمط
+    #[serde(default)]
+    pub audit_log_path: Option<std::path::PathBuf>,
 }
 
 impl Default for SafetyManagerConfig {
@@ -91,6 +119,7 @@ impl Default for SafetyManagerConfig {
             transition_timeout_seconds: 30,
             require_mfa_for_live: true,
             enable_audit_logging: true,
+            audit_log_path: None,
         }
     }
 }
@@ -205,6 +234,10 @@ pub enum ExecutionModeEventType {
     /// Configuration updated
     /// تحديث التكوين
     ConfigurationUpdated,
+    
+    /// Transition refused by the transition rate guard
+    /// رفض الانتقال بواسطة حارس معدل الانتقالات
+    TransitionRateLimited,
 }
 
 /// Safety Check Trait
@@ -286,6 +319,9 @@ pub enum SafetyManagerError {
     
     #[error("Mode transition requires approval")]
     ApprovalRequired,
+    
+    #[error("Too many mode transitions, retry in {retry_after_ms}ms")]
+    TransitionRateExceeded { retry_after_ms: u64 },
 }
 
 /// Result type for safety manager operations
@@ -300,14 +336,40 @@ impl GlobalExecutionSafetyManager {
         
         Self {
             current_mode: Arc::new(RwLock::new(ExecutionMode::DryRun)),
+            mode_snapshot: Arc::new(AtomicU8::new(ExecutionMode::DryRun.as_u8())),
             requirements: Arc::new(RwLock::new(ExecutionRequirements::default())),
             transition_history: Arc::new(Mutex::new(Vec::new())),
             safety_checks: Arc::new(RwLock::new(HashMap::new())),
             event_broadcaster: event_sender,
+            kill_switch: Arc::new(AtomicBool::new(false)),
+            transition_rate_guard: None,
+            audit_log: config
+                .audit_log_path
+                .as_ref()
+                .filter(|_| config.enable_audit_logging)
+                .map(|path| Arc::new(ModeAuditLog::new(path.clone()))),
+            approval_verifier: None,
             config,
         }
     }
 
+    /// Accept Live transitions whose approval token `verifier` resolves to an
+    /// approver other than the requester
+    /// قبول الانتقالات إلى النمط الحي التي يتحقق المدقق من موافقتها من شخص غير الطالب
+    pub fn with_approval_verifier(mut self, verifier: Arc<dyn ApprovalVerifier>) -> Self {
+        self.approval_verifier = Some(verifier);
+        self
+    }
+
+    /// Rate-limit operator transitions to stop mode flapping. Transitions into
+    /// EmergencyStop, through `set_mode` or `emergency_stop`, bypass the guard
+    /// and are never delayed.
+    /// تحديد معدل الانتقالات لمنع تذبذب النمط؛ التوقف الطارئ لا يخضع لهذا الحد
+    pub fn with_transition_rate_guard(mut self, guard: TransitionRateGuard) -> Self {
+        self.transition_rate_guard = Some(Arc::new(guard));
+        self
+    }
+
     /// Initialize the safety manager with default safety checks
     /// تهيئة مدير السلامة مع فحوصص السلامة الافتراضية
     pub async fn initialize(&self) -> SafetyManagerResult<()> {
@@ -318,7 +380,7 @@ impl GlobalExecutionSafetyManager {
         
         // Set initial mode based on environment
         let initial_mode = self.determine_initial_mode().await?;
-        self.set_mode_internal(initial_mode, "system", "Initialization".to_string(), ApprovalStatus::AutoApproved).await?;
+        self.set_mode_internal(initial_mode, "system", "Initialization".to_string(), ApprovalStatus::AutoApproved, Some("system")).await?;
         
         info!("Safety Manager initialized with mode: {:?}", initial_mode);
         Ok(())
@@ -330,6 +392,15 @@ impl GlobalExecutionSafetyManager {
         *self.current_mode.read().await
     }
 
+    /// Current execution mode without taking the mode lock.
+    /// Updated together with the authoritative mode on every transition, so it
+    /// never blocks behind a transition in progress.
+    /// الحصول على النمط الحالي دون أخذ القفل
+    pub fn current_mode_snapshot(&self) -> ExecutionMode {
+        ExecutionMode::from_u8(self.mode_snapshot.load(Ordering::Acquire))
+            .unwrap_or(ExecutionMode::EmergencyStop)
+    }
+
     /// Set execution mode with safety checks
     /// تعيين نمط التنفيذ مع فحوصص السلامة
     pub async fn set_mode(
@@ -337,23 +408,63 @@ impl GlobalExecutionSafetyManager {
         new_mode: ExecutionMode,
         user: &str,
         reason: String,
+    ) -> SafetyManagerResult<()> {
+        self.set_mode_with_context(new_mode, user, reason, new_mode.execution_context()).await
+    }
+
+    /// Set execution mode, running safety checks against a caller-supplied context
+    /// (e.g. one carrying an MFA token)
+    /// تعيين نمط التنفيذ مع تشغيل فحوصص السلامة على سياق يقدمه المستدعي
+    pub async fn set_mode_with_context(
+        &self,
+        new_mode: ExecutionMode,
+        user: &str,
+        reason: String,
+        context: ExecutionContext,
     ) -> SafetyManagerResult<()> {
         info!("Setting execution mode: {:?} by user: {}", new_mode, user);
         
+        // Refuse rapid flapping before doing any other work. The slot is
+        // reserved now and given back if the transition fails below.
+        let permit = match &self.transition_rate_guard {
+            Some(guard) if !new_mode.is_emergency_stop() => match guard.check() {
+                Ok(permit) => Some(permit),
+                Err(retry_after) => {
+                    let retry_after_ms = retry_after.as_millis() as u64;
+                    warn!("Transition to {:?} by {} rate-limited, retry in {}ms", new_mode, user, retry_after_ms);
+                    
+                    let mut event_data = HashMap::new();
+                    event_data.insert("requested_mode".to_string(), serde_json::json!(new_mode.as_str()));
+                    event_data.insert("user".to_string(), serde_json::json!(user));
+                    event_data.insert("retry_after_ms".to_string(), serde_json::json!(retry_after_ms));
+                    self.emit_event(ExecutionModeEventType::TransitionRateLimited, self.current_mode().await, "safety_manager", event_data).await;
+                    
+                    return Err(SafetyManagerError::TransitionRateExceeded { retry_after_ms });
+                }
+            },
+            _ => None,
+        };
+        
         // Validate the transition
         self.validate_mode_transition(new_mode).await?;
         
         // Check permissions
         self.check_permissions(new_mode, user).await?;
         
+        // Live requires MFA even when the other safety checks are disabled
+        self.enforce_mfa(new_mode, &context).await?;
+        
         // Run safety checks
-        self.run_safety_checks(new_mode).await?;
+        self.run_safety_checks(new_mode, &context).await?;
         
         // Handle approval requirements
-        let approval_status = self.handle_approval_requirements(new_mode, user).await?;
+        let (approval_status, approved_by) = self.handle_approval_requirements(new_mode, user, &context).await?;
         
         // Set the mode
-        self.set_mode_internal(new_mode, user, reason, approval_status).await?;
+        self.set_mode_internal(new_mode, user, reason, approval_status, approved_by.as_deref()).await?;
+        if let Some(permit) = permit {
+            permit.commit();
+        }
         
         // Emit event
         self.emit_event(ExecutionModeEventType::ModeChanged, new_mode, "safety_manager", HashMap::new()).await;
@@ -362,13 +473,13 @@ impl GlobalExecutionSafetyManager {
         Ok(())
     }
 
-    /// Emergency stop - immediately switch to safest mode
-    /// التوقف الطارئ - التبديل الفوري إلى النمط الأكثر أمانًا
+    /// Emergency stop - immediately engage the EmergencyStop kill switch
+    /// التوقف الطارئ - التفعيل الفوري لمفتاح الإيقاف الطارئ
     pub async fn emergency_stop(&self, reason: String) -> SafetyManagerResult<()> {
         warn!("Emergency stop triggered: {}", reason);
         
-        let safest_mode = self.get_safest_mode();
-        self.set_mode_internal(safest_mode, "system", format!("Emergency stop: {}", reason), ApprovalStatus::AutoApproved).await?;
+        let safest_mode = ExecutionMode::EmergencyStop;
+        self.set_mode_internal(safest_mode, "system", format!("Emergency stop: {}", reason), ApprovalStatus::AutoApproved, Some("system")).await?;
         
         // Emit emergency stop event
         let mut event_data = HashMap::new();
@@ -379,6 +490,12 @@ impl GlobalExecutionSafetyManager {
         Ok(())
     }
 
+    /// Shared kill switch flag, set while the manager is in EmergencyStop
+    /// علامة مفتاح الإيقاف المشتركة، مفعلة أثناء التوقف الطارئ
+    pub fn kill_switch(&self) -> Arc<AtomicBool> {
+        self.kill_switch.clone()
+    }
+
     /// Validate current mode against requirements
     /// التحقق من النمط الحالي مقابل المتطلبات
     pub async fn validate_current_mode(&self) -> SafetyManagerResult<()> {
@@ -416,6 +533,23 @@ impl GlobalExecutionSafetyManager {
             .collect()
     }
 
+    /// Audited mode transitions between `from_ts` and `to_ts` (inclusive)
+    /// انتقالات النمط المسجلة في المراجعة ضمن النطاق الزمني
+    pub async fn query_audit(
+        &self,
+        from_ts: DateTime<Utc>,
+        to_ts: DateTime<Utc>,
+    ) -> SafetyManagerResult<Vec<ModeAuditEntry>> {
+        let audit_log = self.audit_log.as_ref().ok_or_else(|| {
+            SafetyManagerError::ConfigurationError("audit logging is not configured".to_string())
+        })?;
+        
+        audit_log
+            .query(from_ts, to_ts)
+            .await
+            .map_err(|e| SafetyManagerError::AuditLoggingFailed(e.to_string()))
+    }
+
     /// Update execution requirements
     /// تحديث متطلبات التنفيذ
     pub async fn update_requirements(&self, new_requirements: ExecutionRequirements) -> SafetyManagerResult<()> {
@@ -494,13 +628,33 @@ impl GlobalExecutionSafetyManager {
         initiated_by: &str,
         reason: String,
         approval_status: ApprovalStatus,
+        approved_by: Option<&str>,
     ) -> SafetyManagerResult<()> {
         let old_mode = self.current_mode().await;
+        let timestamp = Utc::now();
         
         // Update current mode
         {
             let mut mode = self.current_mode.write().await;
             *mode = new_mode;
+            self.mode_snapshot.store(new_mode.as_u8(), Ordering::Release);
+        }
+        self.kill_switch.store(new_mode.is_emergency_stop(), Ordering::SeqCst);
+        
+        // Append to the audit trail. A failed write is reported but never undoes
+        // the transition: an emergency stop must not be blocked by a full disk.
+        if let Some(audit_log) = &self.audit_log {
+            let entry = ModeAuditEntry {
+                from: old_mode,
+                to: new_mode,
+                reason: reason.clone(),
+                actor: initiated_by.to_string(),
+                timestamp,
+                approved_by: approved_by.map(str::to_string),
+            };
+            if let Err(e) = audit_log.append(&entry).await {
+                error!("Failed to write mode transition to audit log {:?}: {}", audit_log.path(), e);
+            }
         }
         
         // Record transition
@@ -509,7 +663,7 @@ impl GlobalExecutionSafetyManager {
                 id: uuid::Uuid::new_v4().to_string(),
                 from_mode: old_mode,
                 to_mode: new_mode,
-                timestamp: Utc::now(),
+                timestamp,
                 initiated_by: initiated_by.to_string(),
                 reason,
                 approval_status,
@@ -553,6 +707,13 @@ impl GlobalExecutionSafetyManager {
     fn is_transition_allowed(&self, from: ExecutionMode, to: ExecutionMode) -> bool {
         // Allow all transitions except certain restricted ones
         match (from, to) {
+            // The kill switch can always be engaged
+            (_, ExecutionMode::EmergencyStop) => true,
+            
+            // Leaving EmergencyStop resumes through DryRun only
+            (ExecutionMode::EmergencyStop, ExecutionMode::DryRun) => true,
+            (ExecutionMode::EmergencyStop, _) => false,
+            
             // Allow all transitions from DryRun
             (ExecutionMode::DryRun, _) => true,
             
@@ -587,10 +748,37 @@ impl GlobalExecutionSafetyManager {
             }
         }
         
-        // Check MFA requirement for Live mode
-        if new_mode == ExecutionMode::Live && self.config.require_mfa_for_live {
-            // In a real implementation, this would check MFA status
-            // For now, we'll assume MFA is required but not implemented
+        Ok(())
+    }
+
+    /// Whether `mode` must pass the MFA check regardless of `enable_safety_checks`
+    /// ما إذا كان النمط يتطلب فحص المصادقة متعددة العوامل دائمًا
+    fn mfa_enforced_for(&self, mode: ExecutionMode) -> bool {
+        mode == ExecutionMode::Live && self.config.require_mfa_for_live
+    }
+
+    /// Verify the caller's MFA token for Live; refuse outright when no
+    /// RequiresMfa check is registered to verify it
+    /// التحقق من رمز المصادقة متعددة العوامل للمستدعي عند الانتقال إلى المباشر
+    async fn enforce_mfa(&self, mode: ExecutionMode, context: &ExecutionContext) -> SafetyManagerResult<()> {
+        if !self.mfa_enforced_for(mode) {
+            return Ok(());
+        }
+        
+        let checks = self.safety_checks.read().await;
+        let check = checks
+            .get(REQUIRES_MFA_CHECK_NAME)
+            .ok_or(SafetyManagerError::MFARequired)?;
+        
+        let result = check.check(mode, context).await;
+        if !result.passed {
+            warn!("MFA check refused transition to {:?}: {}", mode, result.message);
+            
+            let mut event_data = HashMap::new();
+            event_data.insert("check_name".to_string(), serde_json::json!(REQUIRES_MFA_CHECK_NAME));
+            event_data.insert("error_message".to_string(), serde_json::Value::String(result.message));
+            self.emit_event(ExecutionModeEventType::SafetyCheckFailed, mode, "safety_manager", event_data).await;
+            
             return Err(SafetyManagerError::MFARequired);
         }
         
@@ -599,16 +787,20 @@ impl GlobalExecutionSafetyManager {
 
     /// Run safety checks for mode
     /// تشغيل فحوصص السلامة للنمط
-    async fn run_safety_checks(&self, mode: ExecutionMode) -> SafetyManagerResult<()> {
+    async fn run_safety_checks(&self, mode: ExecutionMode, context: &ExecutionContext) -> SafetyManagerResult<()> {
         if !self.config.enable_safety_checks {
             return Ok(());
         }
         
-        let context = mode.execution_context();
         let checks = self.safety_checks.read().await;
         
         for (name, check) in checks.iter() {
-            let result = check.check(mode, &context).await;
+            // Already verified by enforce_mfa; running it again would count a second challenge
+            if name == REQUIRES_MFA_CHECK_NAME && self.mfa_enforced_for(mode) {
+                continue;
+            }
+            
+            let result = check.check(mode, context).await;
             
             if !result.passed {
                 let error_msg = format!("Safety check '{}' failed: {}", name, result.message);
@@ -629,18 +821,30 @@ impl GlobalExecutionSafetyManager {
         Ok(())
     }
 
-    /// Handle approval requirements
-    /// التعامل مع متطلبات الموافقة
-    async fn handle_approval_requirements(&self, mode: ExecutionMode, user: &str) -> SafetyManagerResult<ApprovalStatus> {
-        // Live mode always requires approval
+    /// Handle approval requirements, returning the status and who approved
+    /// التعامل مع متطلبات الموافقة وإرجاع الحالة ومن وافق
+    async fn handle_approval_requirements(
+        &self,
+        mode: ExecutionMode,
+        user: &str,
+        context: &ExecutionContext,
+    ) -> SafetyManagerResult<(ApprovalStatus, Option<String>)> {
+        // Live mode always requires a verified approval from someone other than the requester
         if mode == ExecutionMode::Live {
-            // In a real implementation, this would trigger an approval workflow
-            // For now, we'll require manual approval
-            return Err(SafetyManagerError::ApprovalRequired);
+            let (Some(verifier), Some(token)) = (&self.approval_verifier, context.approval_token.as_deref()) else {
+                return Err(SafetyManagerError::ApprovalRequired);
+            };
+            return match verifier.verify(mode, user, token).await {
+                Some(approver) if approver != user => Ok((ApprovalStatus::Approved, Some(approver))),
+                _ => {
+                    warn!("Approval for {:?} requested by {} was not verified", mode, user);
+                    Err(SafetyManagerError::ApprovalRequired)
+                }
+            };
         }
         
         // Other modes can be auto-approved
-        Ok(ApprovalStatus::AutoApproved)
+        Ok((ApprovalStatus::AutoApproved, Some("system".to_string())))
     }
 
     /// Determine initial mode based on environment
@@ -659,12 +863,6 @@ impl GlobalExecutionSafetyManager {
         }
     }
 
-    /// Get the safest mode available
-    /// الحصول على النمط الأكثر أمانًا المتاحر
-    fn get_safest_mode(&self) -> ExecutionMode {
-        ExecutionMode::Backtest // Safest mode
-    }
-
     /// Register default safety checks
     /// تسجيل فحوصص السلامة الافتراضية
     async fn register_default_safety_checks(&self) -> SafetyManagerResult<()> {
@@ -782,7 +980,7 @@ impl SafetyCheck for BasicSafetyCheck {
                 // Additional checks for live mode
                 false // Would implement actual checks
             }
-            ExecutionMode::DryRun | ExecutionMode::Backtest => {
+            ExecutionMode::DryRun | ExecutionMode::Backtest | ExecutionMode::EmergencyStop => {
                 true // Always safe
             }
         };
@@ -962,9 +1160,238 @@ impl SafetyCheck for PermissionCheck {
     }
 }
 
+/// Transition Rate Guard - allows at most `max_transitions` mode changes per
+/// `window`; the next attempt starts a `cooldown` during which all are refused
+/// حارس معدل الانتقالات - يمنع تذبذب النمط بفرض فترة تهدئة
+pub struct TransitionRateGuard {
+    max_transitions: usize,
+    window: std::time::Duration,
+    cooldown: std::time::Duration,
+    state: parking_lot::Mutex<TransitionRateState>,
+}
+
+#[derive(Default)]
+struct TransitionRateState {
+    recent: std::collections::VecDeque<std::time::Instant>,
+    blocked_until: Option<std::time::Instant>,
+}
+
+impl TransitionRateGuard {
+    pub fn new(max_transitions: usize, window: std::time::Duration, cooldown: std::time::Duration) -> Self {
+        Self {
+            max_transitions,
+            window,
+            cooldown,
+            state: parking_lot::Mutex::new(TransitionRateState::default()),
+        }
+    }
+
+    /// Reserve a slot for a transition starting now, or the time left in the
+    /// cooldown. The slot is released unless the permit is committed, so
+    /// concurrent callers can't all pass on the same free slot.
+    /// حجز مكان لانتقال يبدأ الآن، وإلا الوقت المتبقي من فترة التهدئة
+    pub fn check(&self) -> Result<TransitionPermit<'_>, std::time::Duration> {
+        let now = std::time::Instant::now();
+        self.check_at(now)?;
+        Ok(TransitionPermit {
+            guard: self,
+            reserved_at: now,
+            committed: false,
+        })
+    }
+
+    fn check_at(&self, now: std::time::Instant) -> Result<(), std::time::Duration> {
+        let mut state = self.state.lock();
+        
+        if let Some(until) = state.blocked_until {
+            if now < until {
+                return Err(until - now);
+            }
+            state.blocked_until = None;
+        }
+        
+        let window = self.window;
+        state.recent.retain(|t| now.duration_since(*t) < window);
+        if state.recent.len() >= self.max_transitions {
+            // Start afresh once the cooldown is over
+            state.recent.clear();
+            state.blocked_until = Some(now + self.cooldown);
+            return Err(self.cooldown);
+        }
+        
+        state.recent.push_back(now);
+        Ok(())
+    }
+
+    fn release_at(&self, reserved_at: std::time::Instant) {
+        let mut state = self.state.lock();
+        if let Some(index) = state.recent.iter().position(|t| *t == reserved_at) {
+            state.recent.remove(index);
+        }
+    }
+}
+
+/// A slot reserved by [`TransitionRateGuard::check`]; released on drop unless committed
+/// مكان محجوز بواسطة حارس المعدل؛ يُحرر عند الإسقاط ما لم يُثبت
+pub struct TransitionPermit<'a> {
+    guard: &'a TransitionRateGuard,
+    reserved_at: std::time::Instant,
+    committed: bool,
+}
+
+impl TransitionPermit<'_> {
+    /// Count the transition against the window
+    /// احتساب الانتقال ضمن النافذة
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for TransitionPermit<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.guard.release_at(self.reserved_at);
+        }
+    }
+}
+
+/// Verifies approval tokens presented with a transition that needs sign-off
+/// يتحقق من رموز الموافقة المقدمة مع انتقال يتطلب موافقة
+#[async_trait::async_trait]
+pub trait ApprovalVerifier: Send + Sync {
+    /// Identity of the approver if `token` is a valid approval of `requester`
+    /// moving to `mode`, otherwise `None`
+    /// هوية الموافق إذا كان الرمز موافقة صالحة لانتقال الطالب إلى النمط
+    async fn verify(&self, mode: ExecutionMode, requester: &str, token: &str) -> Option<String>;
+}
+
+/// Name under which [`RequiresMfa`] registers
+/// الاسم الذي يُسجل به فحص المصادقة متعددة العوامل
+pub const REQUIRES_MFA_CHECK_NAME: &str = "requires_mfa";
+
+/// Verifies MFA tokens presented with a mode transition
+/// يتحقق من رموز المصادقة متعددة العوامل المقدمة مع انتقال النمط
+#[async_trait::async_trait]
+pub trait MfaVerifier: Send + Sync {
+    /// Whether `token` is a valid, unexpired MFA token
+    /// ما إذا كان الرمز صالحًا وغير منتهي الصلاحية
+    async fn verify(&self, token: &str) -> bool;
+}
+
+/// Requires MFA Check - blocks transitions into `Live` or any mode at or above
+/// a risk level unless the context carries a token the verifier accepts
+/// فحص يتطلب المصادقة متعددة العوامل للانتقال إلى الأنماط الخطرة
+pub struct RequiresMfa {
+    description: String,
+    verifier: Arc<dyn MfaVerifier>,
+    min_risk_level: RiskLevel,
+    monitor: Option<Arc<ExecutionModeMonitor>>,
+}
+
+impl RequiresMfa {
+    pub fn new(verifier: Arc<dyn MfaVerifier>) -> Self {
+        Self {
+            description: "Requires a verified MFA token to enter high-risk modes".to_string(),
+            verifier,
+            min_risk_level: RiskLevel::High,
+            monitor: None,
+        }
+    }
+
+    /// Challenge transitions into modes at or above `level` (default: High)
+    /// تحدي الانتقالات إلى الأنماط عند هذا المستوى أو أعلى
+    pub fn with_min_risk_level(mut self, level: RiskLevel) -> Self {
+        self.min_risk_level = level;
+        self
+    }
+
+    /// Report challenges to a monitor's security metrics
+    /// إبلاغ التحديات إلى مقاييس الأمان في المراقب
+    pub fn with_monitor(mut self, monitor: Arc<ExecutionModeMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    fn is_dangerous(&self, mode: ExecutionMode) -> bool {
+        mode == ExecutionMode::Live || mode.risk_level() >= self.min_risk_level
+    }
+}
+
+#[async_trait::async_trait]
+impl SafetyCheck for RequiresMfa {
+    fn name(&self) -> &str {
+        REQUIRES_MFA_CHECK_NAME
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn check(&self, mode: ExecutionMode, context: &ExecutionContext) -> SafetyCheckResult {
+        let start = std::time::Instant::now();
+        
+        let (passed, message) = if !self.is_dangerous(mode) {
+            (true, format!("MFA not required for {}", mode))
+        } else {
+            let verified = match context.mfa_token.as_deref() {
+                Some(token) => self.verifier.verify(token).await,
+                None => false,
+            };
+            
+            if let Some(monitor) = &self.monitor {
+                monitor.record_mfa_challenge(verified).await;
+            }
+            
+            match (verified, context.mfa_token.is_some()) {
+                (true, _) => (true, "MFA token verified".to_string()),
+                (false, true) => (false, format!("Invalid MFA token for transition to {}", mode)),
+                (false, false) => (false, format!("MFA token required for transition to {}", mode)),
+            }
+        };
+        
+        SafetyCheckResult {
+            check_name: REQUIRES_MFA_CHECK_NAME.to_string(),
+            passed,
+            message,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            details: HashMap::new(),
+        }
+    }
+
+    fn required_risk_level(&self) -> RiskLevel {
+        self.min_risk_level
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::monitoring::MonitorConfig;
+
+    struct StaticVerifier(&'static str);
+
+    #[async_trait::async_trait]
+    impl MfaVerifier for StaticVerifier {
+        async fn verify(&self, token: &str) -> bool {
+            token == self.0
+        }
+    }
+
+    /// Accepts `approval-<approver>` tokens
+    struct TokenApprover;
+
+    #[async_trait::async_trait]
+    impl ApprovalVerifier for TokenApprover {
+        async fn verify(&self, _mode: ExecutionMode, _requester: &str, token: &str) -> Option<String> {
+            token.strip_prefix("approval-").map(str::to_string)
+        }
+    }
+
+    fn mfa_check() -> (RequiresMfa, Arc<ExecutionModeMonitor>) {
+        let monitor = Arc::new(ExecutionModeMonitor::new(MonitorConfig::default()));
+        let check = RequiresMfa::new(Arc::new(StaticVerifier("123456"))).with_monitor(monitor.clone());
+        (check, monitor)
+    }
 
     #[test]
     fn test_execution_mode_properties() {
@@ -1013,6 +1440,317 @@ mod tests {
         assert!(!manager.is_transition_allowed(ExecutionMode::Backtest, ExecutionMode::Live));
     }
 
+    #[test]
+    fn test_execution_mode_tag_round_trip() {
+        for mode in [ExecutionMode::Live, ExecutionMode::DryRun, ExecutionMode::Backtest, ExecutionMode::EmergencyStop] {
+            assert_eq!(ExecutionMode::from_u8(mode.as_u8()), Some(mode));
+        }
+        assert_eq!(ExecutionMode::from_u8(42), None);
+    }
+
+    #[tokio::test]
+    async fn test_mode_snapshot_tracks_transitions() {
+        let manager = Arc::new(GlobalExecutionSafetyManager::new(SafetyManagerConfig::default()));
+        assert_eq!(manager.current_mode_snapshot(), ExecutionMode::DryRun);
+
+        // A concurrent reader spins on the snapshot until it sees the transition
+        let reader = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                while manager.current_mode_snapshot() != ExecutionMode::EmergencyStop {
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        manager.emergency_stop("snapshot test".to_string()).await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(1), reader)
+            .await
+            .expect("reader never observed the transition")
+            .unwrap();
+        assert_eq!(manager.current_mode_snapshot(), manager.current_mode().await);
+
+        // Reading the snapshot doesn't wait on the mode lock
+        let _write_guard = manager.current_mode.write().await;
+        assert_eq!(manager.current_mode_snapshot(), ExecutionMode::EmergencyStop);
+    }
+
+    #[tokio::test]
+    async fn test_transitions_are_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SafetyManagerConfig {
+            audit_log_path: Some(dir.path().join("mode_audit.jsonl")),
+            ..SafetyManagerConfig::default()
+        };
+        let manager = GlobalExecutionSafetyManager::new(config);
+        let before = Utc::now();
+
+        manager.emergency_stop("feed outage".to_string()).await.unwrap();
+        manager
+            .set_mode_internal(ExecutionMode::DryRun, "alice", "resume".to_string(), ApprovalStatus::Approved, Some("bob"))
+            .await
+            .unwrap();
+
+        let entries = manager.query_audit(before, Utc::now()).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].from, ExecutionMode::DryRun);
+        assert_eq!(entries[0].to, ExecutionMode::EmergencyStop);
+        assert_eq!(entries[0].actor, "system");
+        assert_eq!(entries[0].approved_by.as_deref(), Some("system"));
+        assert!(entries[0].reason.contains("feed outage"));
+        assert_eq!(entries[1].to, ExecutionMode::DryRun);
+        assert_eq!(entries[1].actor, "alice");
+        assert_eq!(entries[1].approved_by.as_deref(), Some("bob"));
+
+        // Nothing recorded before the manager started
+        let earlier = manager
+            .query_audit(before - chrono::Duration::hours(1), before - chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        assert!(earlier.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_audit_requires_configured_log() {
+        let manager = GlobalExecutionSafetyManager::new(SafetyManagerConfig::default());
+        assert!(matches!(
+            manager.query_audit(Utc::now(), Utc::now()).await,
+            Err(SafetyManagerError::ConfigurationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_requires_mfa_accepts_verified_token() {
+        let (check, monitor) = mfa_check();
+        let context = ExecutionMode::Live.execution_context().with_mfa_token("123456");
+
+        let result = check.check(ExecutionMode::Live, &context).await;
+        assert!(result.passed, "{}", result.message);
+
+        let security = monitor.get_metrics().await.security_metrics;
+        assert_eq!(security.mfa_challenges, 1);
+        assert_eq!(security.blocked_operations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_requires_mfa_blocks_missing_or_invalid_token() {
+        let (check, monitor) = mfa_check();
+
+        let missing = check.check(ExecutionMode::Live, &ExecutionMode::Live.execution_context()).await;
+        assert!(!missing.passed);
+        assert!(missing.message.contains("required"));
+
+        let invalid_context = ExecutionMode::Live.execution_context().with_mfa_token("000000");
+        let invalid = check.check(ExecutionMode::Live, &invalid_context).await;
+        assert!(!invalid.passed);
+        assert!(invalid.message.contains("Invalid"));
+
+        let security = monitor.get_metrics().await.security_metrics;
+        assert_eq!(security.mfa_challenges, 2);
+        assert_eq!(security.blocked_operations, 2);
+    }
+
+    #[tokio::test]
+    async fn test_requires_mfa_ignores_low_risk_modes() {
+        let (check, monitor) = mfa_check();
+
+        let result = check.check(ExecutionMode::DryRun, &ExecutionMode::DryRun.execution_context()).await;
+        assert!(result.passed);
+        assert_eq!(monitor.get_metrics().await.security_metrics.mfa_challenges, 0);
+    }
+
+    /// Requirements that every mode in `modes` satisfies
+    fn requirements_for(modes: &[ExecutionMode]) -> ExecutionRequirements {
+        let mut requirements = ExecutionRequirements {
+            available_permissions: Vec::new(),
+            available_data_sources: Vec::new(),
+            available_monitoring: Vec::new(),
+            has_risk_management: true,
+            max_risk_level: RiskLevel::High,
+            environment: Environment::Production,
+        };
+        for mode in modes {
+            requirements.available_permissions.extend(mode.required_permissions());
+            requirements.available_data_sources.extend(mode.data_source_requirements());
+            requirements.available_monitoring.extend(mode.monitoring_requirements());
+        }
+        requirements
+    }
+
+    #[tokio::test]
+    async fn test_set_mode_live_enforces_mfa() {
+        for enable_safety_checks in [true, false] {
+            let config = SafetyManagerConfig {
+                enable_safety_checks,
+                require_mfa_for_live: true,
+                ..SafetyManagerConfig::default()
+            };
+            let manager = GlobalExecutionSafetyManager::new(config).with_approval_verifier(Arc::new(TokenApprover));
+            *manager.requirements.write().await = requirements_for(&[ExecutionMode::Live]);
+            let (check, monitor) = mfa_check();
+            manager.register_safety_check(Box::new(check)).await.unwrap();
+
+            let manager = &manager;
+            let go_live = move |context: ExecutionContext| {
+                manager.set_mode_with_context(ExecutionMode::Live, "alice", "go live".to_string(), context)
+            };
+            let approved = || ExecutionMode::Live.execution_context().with_approval_token("approval-bob");
+
+            let missing = go_live(approved()).await;
+            assert!(matches!(missing, Err(SafetyManagerError::MFARequired)), "safety checks {}", enable_safety_checks);
+
+            let invalid = go_live(approved().with_mfa_token("000000")).await;
+            assert!(matches!(invalid, Err(SafetyManagerError::MFARequired)), "safety checks {}", enable_safety_checks);
+            assert_eq!(manager.current_mode().await, ExecutionMode::DryRun);
+
+            go_live(approved().with_mfa_token("123456")).await.unwrap();
+            assert_eq!(manager.current_mode().await, ExecutionMode::Live);
+
+            // Each attempt is challenged exactly once
+            let security = monitor.get_metrics().await.security_metrics;
+            assert_eq!(security.mfa_challenges, 3);
+            assert_eq!(security.blocked_operations, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_mode_live_without_mfa_check_is_refused() {
+        let manager = GlobalExecutionSafetyManager::new(SafetyManagerConfig::default())
+            .with_approval_verifier(Arc::new(TokenApprover));
+        *manager.requirements.write().await = requirements_for(&[ExecutionMode::Live]);
+
+        let context = ExecutionMode::Live.execution_context().with_mfa_token("123456").with_approval_token("approval-bob");
+        let result = manager.set_mode_with_context(ExecutionMode::Live, "alice", "go live".to_string(), context).await;
+        assert!(matches!(result, Err(SafetyManagerError::MFARequired)));
+    }
+
+    /// `manager` with Live requirements met and the MFA check registered
+    async fn live_ready(manager: GlobalExecutionSafetyManager) -> GlobalExecutionSafetyManager {
+        *manager.requirements.write().await = requirements_for(&[ExecutionMode::Live]);
+        manager.register_safety_check(Box::new(mfa_check().0)).await.unwrap();
+        manager
+    }
+
+    async fn go_live(manager: &GlobalExecutionSafetyManager, approval_token: &str) -> SafetyManagerResult<()> {
+        let context = ExecutionMode::Live
+            .execution_context()
+            .with_mfa_token("123456")
+            .with_approval_token(approval_token);
+        manager.set_mode_with_context(ExecutionMode::Live, "alice", "go live".to_string(), context).await
+    }
+
+    #[tokio::test]
+    async fn test_live_approval_must_be_verified() {
+        // Without a verifier no token approves Live
+        let manager = live_ready(GlobalExecutionSafetyManager::new(SafetyManagerConfig::default())).await;
+        assert!(matches!(go_live(&manager, "approval-bob").await, Err(SafetyManagerError::ApprovalRequired)));
+
+        let manager = live_ready(
+            GlobalExecutionSafetyManager::new(SafetyManagerConfig::default()).with_approval_verifier(Arc::new(TokenApprover)),
+        )
+        .await;
+        assert!(matches!(go_live(&manager, "bogus").await, Err(SafetyManagerError::ApprovalRequired)));
+        // Approving your own transition doesn't count
+        assert!(matches!(go_live(&manager, "approval-alice").await, Err(SafetyManagerError::ApprovalRequired)));
+        assert_eq!(manager.current_mode().await, ExecutionMode::DryRun);
+
+        go_live(&manager, "approval-bob").await.unwrap();
+        assert_eq!(manager.current_mode().await, ExecutionMode::Live);
+    }
+
+    #[test]
+    fn test_mfa_token_is_not_serialized() {
+        let context = ExecutionMode::Live.execution_context().with_mfa_token("123456");
+        let json = serde_json::to_string(&context).unwrap();
+        assert!(!json.contains("123456"));
+    }
+
+    #[test]
+    fn test_transition_rate_guard_blocks_until_cooldown() {
+        use std::time::{Duration, Instant};
+
+        let guard = TransitionRateGuard::new(3, Duration::from_secs(60), Duration::from_secs(30));
+        let start = Instant::now();
+
+        // The Nth transition inside the window is still allowed
+        for i in 0..3 {
+            let now = start + Duration::from_secs(i);
+            assert!(guard.check_at(now).is_ok(), "transition {} should pass", i + 1);
+        }
+
+        // The (N+1)th is refused and starts the cooldown
+        assert_eq!(guard.check_at(start + Duration::from_secs(5)), Err(Duration::from_secs(30)));
+        assert_eq!(guard.check_at(start + Duration::from_secs(20)), Err(Duration::from_secs(15)));
+
+        // Allowed again once the cooldown has elapsed
+        assert!(guard.check_at(start + Duration::from_secs(35)).is_ok());
+    }
+
+    #[test]
+    fn test_transition_rate_guard_window_slides() {
+        use std::time::{Duration, Instant};
+
+        let guard = TransitionRateGuard::new(2, Duration::from_secs(10), Duration::from_secs(30));
+        let start = Instant::now();
+        guard.check_at(start).unwrap();
+        guard.check_at(start + Duration::from_secs(1)).unwrap();
+
+        // Both earlier transitions have left the window
+        assert!(guard.check_at(start + Duration::from_secs(12)).is_ok());
+    }
+
+    async fn rate_limited_manager(max_transitions: usize) -> GlobalExecutionSafetyManager {
+        let guard = TransitionRateGuard::new(max_transitions, std::time::Duration::from_secs(60), std::time::Duration::from_millis(50));
+        let manager = GlobalExecutionSafetyManager::new(SafetyManagerConfig::default()).with_transition_rate_guard(guard);
+        *manager.requirements.write().await = requirements_for(&[ExecutionMode::DryRun, ExecutionMode::Backtest]);
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_set_mode_is_rate_limited() {
+        let manager = rate_limited_manager(2).await;
+        let mut events = manager.subscribe_events();
+
+        manager.set_mode(ExecutionMode::Backtest, "operator", "flap".to_string()).await.unwrap();
+        manager.set_mode(ExecutionMode::DryRun, "operator", "flap".to_string()).await.unwrap();
+        let blocked = manager.set_mode(ExecutionMode::Backtest, "operator", "flap".to_string()).await;
+        assert!(matches!(blocked, Err(SafetyManagerError::TransitionRateExceeded { .. })));
+        assert_eq!(manager.current_mode().await, ExecutionMode::DryRun);
+
+        let mut rate_limited = false;
+        while let Ok(event) = events.try_recv() {
+            rate_limited |= event.event_type == ExecutionModeEventType::TransitionRateLimited;
+        }
+        assert!(rate_limited);
+
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+        assert!(manager.set_mode(ExecutionMode::Backtest, "operator", "settled".to_string()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_emergency_stop_bypasses_rate_guard() {
+        let manager = rate_limited_manager(1).await;
+
+        manager.set_mode(ExecutionMode::Backtest, "operator", "flap".to_string()).await.unwrap();
+        assert!(manager.set_mode(ExecutionMode::DryRun, "operator", "flap".to_string()).await.is_err());
+
+        // Still in the cooldown, yet the operator can always engage the kill switch
+        manager.set_mode(ExecutionMode::EmergencyStop, "operator", "halt".to_string()).await.unwrap();
+        assert_eq!(manager.current_mode().await, ExecutionMode::EmergencyStop);
+    }
+
+    #[tokio::test]
+    async fn test_failed_transition_releases_rate_slot() {
+        let manager = rate_limited_manager(1).await;
+
+        // Refused by requirement validation, so the reserved slot must be given back
+        let refused = manager.set_mode(ExecutionMode::Live, "operator", "too early".to_string()).await;
+        assert!(refused.is_err());
+        assert!(!matches!(refused, Err(SafetyManagerError::TransitionRateExceeded { .. })));
+
+        manager.set_mode(ExecutionMode::Backtest, "operator", "first".to_string()).await.unwrap();
+    }
+
     #[test]
     fn test_approval_status() {
         assert_eq!(format!("{:?}", ApprovalStatus::Pending), "Pending");
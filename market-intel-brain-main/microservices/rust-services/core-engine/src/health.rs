@@ -0,0 +1,486 @@
+//! Rollup health score for the Core Engine
+//!
+//! Combines dependency check results and per-component error rates into a
+//! single 0-100 score with a per-component breakdown. Dependencies weigh
+//! twice as much as error-rate components; a critical dependency that is down
+//! makes the engine unhealthy regardless of the score.
+//!
+//! [`Readiness`] is separate from the score: it answers whether startup has
+//! finished, not how well the running engine is doing. [`EngineHealth`] feeds
+//! the running engine's real component states into the aggregator.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Scores at or above this are `Healthy`
+pub const HEALTHY_THRESHOLD: u8 = 80;
+
+/// Scores at or above this (and below `HEALTHY_THRESHOLD`) are `Degraded`
+pub const DEGRADED_THRESHOLD: u8 = 50;
+
+/// Error rate at which a component scores 0; lower rates scale linearly
+pub const MAX_ERROR_RATE: f64 = 0.10;
+
+const DEPENDENCY_WEIGHT: f64 = 2.0;
+const ERROR_RATE_WEIGHT: f64 = 1.0;
+
+/// Result of a single dependency health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyStatus {
+    Up,
+    Degraded,
+    Down,
+}
+
+impl DependencyStatus {
+    fn score(self) -> f64 {
+        match self {
+            DependencyStatus::Up => 100.0,
+            DependencyStatus::Degraded => 50.0,
+            DependencyStatus::Down => 0.0,
+        }
+    }
+}
+
+/// Overall health derived from the score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Degraded => "degraded",
+            HealthStatus::Unhealthy => "unhealthy",
+        }
+    }
+
+    fn from_score(score: u8) -> Self {
+        if score >= HEALTHY_THRESHOLD {
+            HealthStatus::Healthy
+        } else if score >= DEGRADED_THRESHOLD {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Unhealthy
+        }
+    }
+}
+
+/// One component's contribution to the rollup
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentScore {
+    pub name: String,
+    /// 0-100
+    pub score: f64,
+    pub weight: f64,
+}
+
+/// Output of [`HealthAggregator::evaluate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// 0-100
+    pub score: u8,
+    pub status: HealthStatus,
+    pub breakdown: Vec<ComponentScore>,
+}
+
+impl HealthReport {
+    /// Flatten into the `details` map of a `HealthCheckResponse`
+    pub fn to_details(&self) -> HashMap<String, String> {
+        let mut details: HashMap<String, String> = self
+            .breakdown
+            .iter()
+            .map(|c| (format!("score.{}", c.name), format!("{:.0}", c.score)))
+            .collect();
+        details.insert("score".to_string(), self.score.to_string());
+        details.insert("status".to_string(), self.status.as_str().to_string());
+        details
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Component {
+    Dependency {
+        name: String,
+        status: DependencyStatus,
+        critical: bool,
+    },
+    ErrorRate {
+        name: String,
+        rate: f64,
+    },
+}
+
+/// Collects component states and computes a [`HealthReport`]
+///
+/// ```ignore
+/// let report = HealthAggregator::new()
+///     .dependency("postgres", DependencyStatus::Up, true)
+///     .error_rate("analytics", 0.01)
+///     .error_rate("vector_store", 0.0)
+///     .error_rate("stream", 0.02)
+///     .evaluate();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HealthAggregator {
+    components: Vec<Component>,
+}
+
+impl HealthAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a dependency check result. A `critical` dependency that is `Down`
+    /// forces the overall status to `Unhealthy`.
+    pub fn dependency(mut self, name: impl Into<String>, status: DependencyStatus, critical: bool) -> Self {
+        self.components.push(Component::Dependency {
+            name: name.into(),
+            status,
+            critical,
+        });
+        self
+    }
+
+    /// Add a component's recent error rate (0.0-1.0)
+    pub fn error_rate(mut self, name: impl Into<String>, rate: f64) -> Self {
+        self.components.push(Component::ErrorRate {
+            name: name.into(),
+            rate,
+        });
+        self
+    }
+
+    /// Weighted average of component scores. With no components the engine
+    /// is considered fully healthy.
+    pub fn evaluate(&self) -> HealthReport {
+        let mut breakdown = Vec::with_capacity(self.components.len());
+        let mut critical_down = false;
+
+        for component in &self.components {
+            match component {
+                Component::Dependency { name, status, critical } => {
+                    critical_down |= *critical && *status == DependencyStatus::Down;
+                    breakdown.push(ComponentScore {
+                        name: name.clone(),
+                        score: status.score(),
+                        weight: DEPENDENCY_WEIGHT,
+                    });
+                }
+                Component::ErrorRate { name, rate } => {
+                    // NaN (e.g. 0/0 with no traffic) counts as no errors
+                    let rate = if rate.is_nan() { 0.0 } else { rate.clamp(0.0, 1.0) };
+                    let score = 100.0 * (1.0 - (rate / MAX_ERROR_RATE).min(1.0));
+                    breakdown.push(ComponentScore {
+                        name: name.clone(),
+                        score,
+                        weight: ERROR_RATE_WEIGHT,
+                    });
+                }
+            }
+        }
+
+        let total_weight: f64 = breakdown.iter().map(|c| c.weight).sum();
+        let score = if total_weight > 0.0 {
+            let weighted: f64 = breakdown.iter().map(|c| c.score * c.weight).sum();
+            (weighted / total_weight).round().clamp(0.0, 100.0) as u8
+        } else {
+            100
+        };
+
+        let status = if critical_down {
+            HealthStatus::Unhealthy
+        } else {
+            HealthStatus::from_score(score)
+        };
+
+        HealthReport { score, status, breakdown }
+    }
+}
+
+/// Reports whether a dependency is ready each time readiness is asked
+type ReadinessCheck = Box<dyn Fn() -> bool + Send + Sync>;
+
+/// Startup dependencies that must finish initializing before the engine
+/// accepts traffic. Liveness never consults this.
+#[derive(Default)]
+pub struct Readiness {
+    components: BTreeSet<String>,
+    pending: parking_lot::Mutex<BTreeSet<String>>,
+    checks: Vec<(String, ReadinessCheck)>,
+}
+
+impl std::fmt::Debug for Readiness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Readiness")
+            .field("components", &self.components)
+            .field("pending", &self.pending)
+            .field("checks", &self.checks.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Readiness {
+    /// Not ready until every named component has called [`mark_ready`](Self::mark_ready)
+    pub fn new<I, S>(components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let components: BTreeSet<String> = components.into_iter().map(Into::into).collect();
+        Self {
+            pending: parking_lot::Mutex::new(components.clone()),
+            components,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Also require `component`, which is ready whenever `check` returns true.
+    /// Use this for dependencies that come and go at runtime rather than
+    /// finishing once at startup.
+    pub fn with_check(mut self, component: impl Into<String>, check: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        self.checks.push((component.into(), Box::new(check)));
+        self
+    }
+
+    /// Record that `component` finished initializing. Repeated calls and
+    /// unknown names are ignored.
+    pub fn mark_ready(&self, component: &str) {
+        self.pending.lock().remove(component);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.pending.lock().is_empty() && self.checks.iter().all(|(_, check)| check())
+    }
+
+    /// Components still initializing, sorted by name
+    pub fn pending(&self) -> Vec<String> {
+        self.components()
+            .into_iter()
+            .filter(|(_, ready)| !ready)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Every tracked component and whether it is ready, sorted by name
+    pub fn components(&self) -> Vec<(String, bool)> {
+        let mut components: Vec<(String, bool)> = {
+            let pending = self.pending.lock();
+            self.components
+                .iter()
+                .map(|name| (name.clone(), !pending.contains(name)))
+                .collect()
+        };
+        components.extend(self.checks.iter().map(|(name, check)| (name.clone(), check())));
+        components.sort();
+        components
+    }
+}
+
+/// Rollup health of the running engine, evaluated on demand (e.g. per scrape)
+///
+/// Each startup component is a critical dependency that is `Up` once it has
+/// initialized. The `grpc` component is the share of calls that did not end
+/// in `Ok` since the previous evaluation.
+#[derive(Debug)]
+pub struct EngineHealth {
+    readiness: Arc<Readiness>,
+    /// (total, failed) call counts seen by the previous evaluation
+    last_calls: parking_lot::Mutex<(u64, u64)>,
+}
+
+impl EngineHealth {
+    pub fn new(readiness: Arc<Readiness>) -> Self {
+        Self {
+            readiness,
+            last_calls: parking_lot::Mutex::new((0, 0)),
+        }
+    }
+
+    pub fn readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    pub fn evaluate(&self) -> HealthReport {
+        let (total, failed) = crate::metrics::prometheus::request_counts();
+        let error_rate = {
+            let mut last = self.last_calls.lock();
+            let calls = total.saturating_sub(last.0);
+            let failures = failed.saturating_sub(last.1);
+            *last = (total, failed);
+            if calls == 0 {
+                0.0
+            } else {
+                failures as f64 / calls as f64
+            }
+        };
+
+        let mut aggregator = HealthAggregator::new();
+        for (name, ready) in self.readiness.components() {
+            let status = if ready { DependencyStatus::Up } else { DependencyStatus::Down };
+            aggregator = aggregator.dependency(name, status, true);
+        }
+        aggregator.error_rate("grpc", error_rate).evaluate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_up_and_no_errors_is_perfect() {
+        let report = HealthAggregator::new()
+            .dependency("postgres", DependencyStatus::Up, true)
+            .dependency("redis", DependencyStatus::Up, false)
+            .error_rate("analytics", 0.0)
+            .error_rate("vector_store", 0.0)
+            .error_rate("stream", 0.0)
+            .evaluate();
+
+        assert_eq!(report.score, 100);
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert_eq!(report.breakdown.len(), 5);
+    }
+
+    #[test]
+    fn no_components_is_healthy() {
+        let report = HealthAggregator::new().evaluate();
+        assert_eq!(report.score, 100);
+        assert_eq!(report.status, HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn degraded_dependency_and_errors_lower_the_score() {
+        // (50 * 2 + 50 * 1) / 3 = 50
+        let report = HealthAggregator::new()
+            .dependency("redis", DependencyStatus::Degraded, false)
+            .error_rate("stream", 0.05)
+            .evaluate();
+
+        assert_eq!(report.score, 50);
+        assert_eq!(report.status, HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn error_rate_at_or_above_max_scores_zero() {
+        let report = HealthAggregator::new()
+            .error_rate("analytics", MAX_ERROR_RATE)
+            .error_rate("vector_store", 0.9)
+            .evaluate();
+
+        assert_eq!(report.score, 0);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn mixed_components_map_to_expected_score() {
+        // deps: 100*2 + 0*2, rates: 90 + 100 + 80 -> 470 / 7 = 67.1
+        let report = HealthAggregator::new()
+            .dependency("postgres", DependencyStatus::Up, true)
+            .dependency("kafka", DependencyStatus::Down, false)
+            .error_rate("analytics", 0.01)
+            .error_rate("vector_store", 0.0)
+            .error_rate("stream", 0.02)
+            .evaluate();
+
+        assert_eq!(report.score, 67);
+        assert_eq!(report.status, HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn critical_dependency_down_is_unhealthy() {
+        let report = HealthAggregator::new()
+            .dependency("postgres", DependencyStatus::Down, true)
+            .error_rate("analytics", 0.0)
+            .error_rate("vector_store", 0.0)
+            .error_rate("stream", 0.0)
+            .error_rate("ingestion", 0.0)
+            .error_rate("sentiment", 0.0)
+            .error_rate("api", 0.0)
+            .error_rate("cache", 0.0)
+            .error_rate("search", 0.0)
+            .evaluate();
+
+        // The score alone would read as healthy
+        assert!(report.score >= HEALTHY_THRESHOLD);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn nan_error_rate_counts_as_no_errors() {
+        let report = HealthAggregator::new().error_rate("stream", f64::NAN).evaluate();
+        assert_eq!(report.score, 100);
+    }
+
+    #[test]
+    fn details_include_score_status_and_breakdown() {
+        let details = HealthAggregator::new()
+            .dependency("postgres", DependencyStatus::Degraded, true)
+            .evaluate()
+            .to_details();
+
+        assert_eq!(details["score"], "50");
+        assert_eq!(details["status"], "degraded");
+        assert_eq!(details["score.postgres"], "50");
+    }
+
+    #[test]
+    fn readiness_waits_for_every_component() {
+        let readiness = Readiness::new(["analytics", "vector_store", "data_source"]);
+        assert!(!readiness.is_ready());
+
+        readiness.mark_ready("analytics");
+        readiness.mark_ready("analytics");
+        readiness.mark_ready("data_source");
+        assert_eq!(readiness.pending(), vec!["vector_store".to_string()]);
+        assert!(!readiness.is_ready());
+
+        readiness.mark_ready("vector_store");
+        assert!(readiness.is_ready());
+        assert!(Readiness::default().is_ready());
+    }
+
+    #[test]
+    fn readiness_check_is_evaluated_on_every_query() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let has_source = Arc::new(AtomicBool::new(false));
+        let flag = has_source.clone();
+        let readiness = Readiness::new(["analytics"]).with_check("data_source", move || flag.load(Ordering::SeqCst));
+
+        readiness.mark_ready("analytics");
+        assert!(!readiness.is_ready());
+        assert_eq!(readiness.pending(), vec!["data_source".to_string()]);
+
+        has_source.store(true, Ordering::SeqCst);
+        assert!(readiness.is_ready());
+        assert!(readiness.pending().is_empty());
+
+        has_source.store(false, Ordering::SeqCst);
+        assert!(!readiness.is_ready());
+    }
+
+    #[test]
+    fn engine_health_marks_uninitialized_components_down() {
+        let readiness = Arc::new(Readiness::new(["analytics", "vector_store"]));
+        readiness.mark_ready("analytics");
+        let report = EngineHealth::new(readiness.clone()).evaluate();
+
+        let scores: HashMap<_, _> = report.breakdown.iter().map(|c| (c.name.as_str(), c.score)).collect();
+        assert_eq!(scores["analytics"], 100.0);
+        assert_eq!(scores["vector_store"], 0.0);
+        assert!(scores.contains_key("grpc"));
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+
+        readiness.mark_ready("vector_store");
+        assert_eq!(
+            readiness.components(),
+            vec![("analytics".to_string(), true), ("vector_store".to_string(), true)]
+        );
+    }
+}
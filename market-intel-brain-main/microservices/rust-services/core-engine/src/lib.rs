@@ -1,17 +1,25 @@
+pub mod agent_config;
 pub mod analytics;
 pub mod config;
 pub mod core_engine_service;
 pub mod data_ingestion;
 pub mod database;
+pub mod errors;
 pub mod execution_safety;
+pub mod health;
 pub mod metrics;
+pub mod metrics_server;
+pub mod middleware;
 pub mod otel;
 pub mod proto;
+pub mod responses;
 pub mod tls;
+pub mod utils;
 pub mod vector_store;
 
 // Re-export commonly used items
 pub use config::*;
 pub use core_engine_service::*;
 pub use database::*;
+pub use errors::{EngineError, EngineResult};
 pub use tls::*;
\ No newline at end of file
@@ -1,34 +1,142 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use tonic::transport::Server;
+use tower::ServiceBuilder;
 use tracing::{info, warn};
+use core_engine::agent_config::AgentConfigurationFile;
 use core_engine::analytics;
-use core_engine::config::CoreEngineConfig;
+use core_engine::config::{ConfigReloader, CoreEngineConfig};
 use core_engine::core_engine_service::CoreEngineServiceImpl;
+use core_engine::data_ingestion::{DataIngestionResult, DataIngestionService};
+use core_engine::health::Readiness;
+use core_engine::metrics_server;
+use core_engine::middleware::{LoadShedLayer, RequestLogLayer, RequestTimeoutLayer};
 use core_engine::otel;
 use core_engine::vector_store;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    otel::init_telemetry("core-engine", env!("CARGO_PKG_VERSION"))?;
-    analytics::init();
-    vector_store::init();
-    info!("Starting Core Engine v{}", env!("CARGO_PKG_VERSION"));
-    let config = CoreEngineConfig::from_env()
+    // Offline config check: no telemetry, no listener
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--validate-config") {
+        let code = match args.get(pos + 1) {
+            Some(path) => validate_config(path),
+            None => {
+                eprintln!("usage: core-engine --validate-config <path>");
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // Config comes first because it decides whether telemetry is mandatory
+    let config_path = std::env::var("CORE_ENGINE_CONFIG")
+        .unwrap_or_else(|_| "config/app.toml".to_string());
+    let config = CoreEngineConfig::load(&config_path)
         .map_err(|e| format!("Failed to load config: {}", e))?;
-    let svc = CoreEngineServiceImpl::new(config.clone()).await?;
+    otel::init_telemetry(
+        "core-engine",
+        env!("CARGO_PKG_VERSION"),
+        &config.logging.level,
+        config.logging.telemetry_required,
+    )?;
+    info!("Starting Core Engine v{}", env!("CARGO_PKG_VERSION"));
+
+    // Probes come up first so /healthz answers while dependencies initialize.
+    // Not ready until the startup components are up and a data source exists.
+    let ingestion = Arc::new(DataIngestionService::new()?);
+    let readiness = Arc::new(startup_readiness(&config, &ingestion));
+    if config.metrics.enabled {
+        let addr = SocketAddr::from(([0, 0, 0, 0], config.metrics.port));
+        let server = metrics_server::serve(addr, config.metrics.endpoint.clone(), readiness.clone());
+        tokio::spawn(async move {
+            if let Err(e) = server.await {
+                warn!("Metrics server stopped: {}", e);
+            }
+        });
+    }
+    analytics::init();
+    readiness.mark_ready("analytics");
+    if config.vector_store.enabled {
+        vector_store::init();
+        readiness.mark_ready("vector_store");
+    }
+    register_data_sources(&ingestion, &config.data_ingestion.sources).await?;
+    let reloader = Arc::new(
+        ConfigReloader::new(&config_path, config.clone())
+            .with_log_level_hook(|level| otel::set_log_level(level).map_err(|e| e.to_string())),
+    );
+    let _reload_task = reloader.start(Duration::from_secs(5));
+    let svc = CoreEngineServiceImpl::new(config.clone(), ingestion.clone()).await?;
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.grpc_port));
     info!("gRPC listening on {}", addr);
     warn!("TLS disabled - NOT FOR PRODUCTION");
     Server::builder()
+        .layer(
+            ServiceBuilder::new()
+                .layer(RequestLogLayer::from_level_name(&config.logging.request_level))
+                .layer(LoadShedLayer::new(config.server.max_in_flight))
+                .layer(RequestTimeoutLayer::new(Duration::from_millis(
+                    config.server.request_timeout_ms,
+                ))),
+        )
         .add_service(svc.into_service())
         .serve_with_shutdown(addr, shutdown_signal())
         .await?;
     otel::shutdown_telemetry();
     analytics::cleanup();
-    vector_store::cleanup();
+    if config.vector_store.enabled {
+        vector_store::cleanup();
+    }
+    Ok(())
+}
+
+/// Readiness for the startup components: analytics, the vector store when it
+/// is enabled, and at least one registered data source.
+fn startup_readiness(config: &CoreEngineConfig, ingestion: &Arc<DataIngestionService>) -> Readiness {
+    let mut components = vec!["analytics"];
+    if config.vector_store.enabled {
+        components.push("vector_store");
+    }
+    let ingestion = ingestion.clone();
+    Readiness::new(components).with_check("data_source", move || ingestion.has_sources())
+}
+
+/// Register the configured data sources with the ingestion service.
+async fn register_data_sources(ingestion: &DataIngestionService, sources: &[String]) -> DataIngestionResult<()> {
+    if sources.is_empty() {
+        warn!("No data sources configured; the engine will not report ready until one is added");
+    }
+    for source in sources {
+        ingestion.add_source(source.clone()).await?;
+    }
     Ok(())
 }
 
+/// Load and validate an agent configuration file, printing the findings.
+/// Returns the process exit code.
+fn validate_config(path: &str) -> i32 {
+    let config = match AgentConfigurationFile::load_from_file(path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+
+    let result = config.validate();
+    for error in &result.errors {
+        eprintln!("error [{}] {}: {}", error.code, error.field_path, error.message);
+    }
+    for warning in &result.warnings {
+        println!("warning [{}] {}: {}", warning.code, warning.field_path, warning.message);
+    }
+    println!("{}", result.summary);
+
+    if result.is_valid { 0 } else { 1 }
+}
+
 async fn shutdown_signal() {
     #[cfg(unix)] {
         use tokio::signal::unix::{signal, SignalKind};
@@ -43,3 +151,33 @@ async fn shutdown_signal() {
         let _ = tokio::signal::ctrl_c().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_readiness_flips_once_a_data_source_is_registered() {
+        let mut config = CoreEngineConfig::default();
+        config.data_ingestion.sources = vec!["yahoo_finance".to_string()];
+        let ingestion = Arc::new(DataIngestionService::new().unwrap());
+        let readiness = startup_readiness(&config, &ingestion);
+
+        readiness.mark_ready("analytics");
+        readiness.mark_ready("vector_store");
+        assert_eq!(readiness.pending(), vec!["data_source".to_string()]);
+
+        register_data_sources(&ingestion, &config.data_ingestion.sources).await.unwrap();
+        assert!(readiness.is_ready());
+    }
+
+    #[test]
+    fn test_disabled_vector_store_is_not_required() {
+        let mut config = CoreEngineConfig::default();
+        config.vector_store.enabled = false;
+        let ingestion = Arc::new(DataIngestionService::new().unwrap());
+
+        let pending = startup_readiness(&config, &ingestion).pending();
+        assert_eq!(pending, vec!["analytics".to_string(), "data_source".to_string()]);
+    }
+}
@@ -40,10 +40,45 @@ pub fn record_error() {
 
 /// Prometheus metrics exporter
 pub mod prometheus {
-    use prometheus::{Encoder, TextEncoder, Counter, Histogram, Registry, Opts, HistogramOpts};
+    use prometheus::core::Collector;
+    use prometheus::proto::MetricType;
+    use prometheus::{Encoder, TextEncoder, Counter, GaugeVec, Histogram, HistogramVec, Registry, Opts, HistogramOpts};
+    use std::collections::BTreeMap;
     use std::sync::OnceLock;
+    use std::time::{Duration, Instant};
+
+    use crate::health::HealthReport;
 
     static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    static HEALTH_SCORE: OnceLock<GaugeVec> = OnceLock::new();
+    static REQUEST_LATENCY: OnceLock<HistogramVec> = OnceLock::new();
+
+    const REQUEST_DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+    fn health_score_gauge() -> &'static GaugeVec {
+        HEALTH_SCORE.get_or_init(|| {
+            GaugeVec::new(
+                Opts::new("health_score", "Rollup health score (0-100); component=\"overall\" is the combined score")
+                    .namespace("market_intel")
+                    .subsystem("core_engine"),
+                &["component"],
+            )
+            .unwrap()
+        })
+    }
+
+    fn request_latency_histogram() -> &'static HistogramVec {
+        REQUEST_LATENCY.get_or_init(|| {
+            HistogramVec::new(
+                HistogramOpts::new("grpc_request_latency_seconds", "gRPC request latency in seconds by method and status code")
+                    .namespace("market_intel")
+                    .subsystem("core_engine")
+                    .buckets(REQUEST_DURATION_BUCKETS.to_vec()),
+                &["method", "code"],
+            )
+            .unwrap()
+        })
+    }
 
     pub fn get_registry() -> &'static Registry {
         REGISTRY.get_or_init(|| {
@@ -60,7 +95,7 @@ pub mod prometheus {
                 HistogramOpts::new("grpc_request_duration_seconds", "gRPC request duration in seconds")
                     .namespace("market_intel")
                     .subsystem("core_engine")
-                    .buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0])
+                    .buckets(REQUEST_DURATION_BUCKETS.to_vec())
             );
             
             let error_counter = Counter::with_opts(
@@ -72,6 +107,8 @@ pub mod prometheus {
             registry.register(Box::new(request_counter)).unwrap();
             registry.register(Box::new(request_duration)).unwrap();
             registry.register(Box::new(error_counter)).unwrap();
+            registry.register(Box::new(health_score_gauge().clone())).unwrap();
+            registry.register(Box::new(request_latency_histogram().clone())).unwrap();
             
             registry
         })
@@ -99,10 +136,250 @@ pub mod prometheus {
         histogram.observe(duration);
     }
 
+    /// Record one call's latency under its method path and resulting gRPC
+    /// status code, so failing and succeeding calls get separate series
+    pub fn record_request_latency(method: &str, code: tonic::Code, seconds: f64) {
+        get_registry();
+        let code = format!("{:?}", code);
+        request_latency_histogram()
+            .with_label_values(&[method, &code])
+            .observe(seconds);
+    }
+
+    /// Calls recorded by [`record_request_latency`] so far, as (total, not `Ok`)
+    pub fn request_counts() -> (u64, u64) {
+        let mut total = 0;
+        let mut failed = 0;
+        for family in request_latency_histogram().collect() {
+            for metric in family.get_metric() {
+                let count = metric.get_histogram().get_sample_count();
+                total += count;
+                if metric.get_label().iter().any(|l| l.get_name() == "code" && l.get_value() != "Ok") {
+                    failed += count;
+                }
+            }
+        }
+        (total, failed)
+    }
+
     /// Increment error counter
     pub fn increment_error_counter(method: &str, error_type: &str) {
         let registry = get_registry();
         let counter = registry.get_metric::<Counter>("grpc_errors_total").unwrap();
         counter.inc();
     }
+
+    /// Label of the combined score in the health gauge
+    pub const OVERALL_HEALTH_COMPONENT: &str = "overall";
+
+    /// Publish a health report: the overall score plus one series per component.
+    /// A component that is itself named "overall" is exported as
+    /// "component:overall" so it can't overwrite the combined score.
+    pub fn record_health(report: &HealthReport) {
+        // Registers the gauge so it shows up in export_metrics()
+        get_registry();
+        let gauge = health_score_gauge();
+        gauge.with_label_values(&[OVERALL_HEALTH_COMPONENT]).set(report.score as f64);
+        for component in &report.breakdown {
+            if component.name == OVERALL_HEALTH_COMPONENT {
+                let label = format!("component:{}", component.name);
+                gauge.with_label_values(&[&label]).set(component.score);
+            } else {
+                gauge.with_label_values(&[&component.name]).set(component.score);
+            }
+        }
+    }
+
+    /// Cumulative values of every counter and histogram series at one point
+    /// in time. Series are keyed like the exposition format, e.g.
+    /// `grpc_requests_total` or `grpc_request_latency_seconds_count{code="Ok",method="/x"}`;
+    /// histograms contribute their `_count` and `_sum`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MetricsSnapshot {
+        pub taken_at: Instant,
+        pub values: BTreeMap<String, f64>,
+    }
+
+    /// Per-series change between two snapshots
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MetricsDelta {
+        pub interval: Duration,
+        pub deltas: BTreeMap<String, f64>,
+    }
+
+    impl MetricsSnapshot {
+        /// Snapshot of the engine's registry
+        pub fn capture() -> Self {
+            Self::from_registry(get_registry())
+        }
+
+        pub fn from_registry(registry: &Registry) -> Self {
+            let mut values = BTreeMap::new();
+            for family in registry.gather() {
+                let name = family.get_name();
+                for metric in family.get_metric() {
+                    let labels = metric
+                        .get_label()
+                        .iter()
+                        .map(|l| format!("{}=\"{}\"", l.get_name(), l.get_value()))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    let key = |suffix: &str| {
+                        if labels.is_empty() {
+                            format!("{}{}", name, suffix)
+                        } else {
+                            format!("{}{}{{{}}}", name, suffix, labels)
+                        }
+                    };
+
+                    match family.get_field_type() {
+                        MetricType::COUNTER => {
+                            values.insert(key(""), metric.get_counter().get_value());
+                        }
+                        MetricType::HISTOGRAM => {
+                            let histogram = metric.get_histogram();
+                            values.insert(key("_count"), histogram.get_sample_count() as f64);
+                            values.insert(key("_sum"), histogram.get_sample_sum());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            Self {
+                taken_at: Instant::now(),
+                values,
+            }
+        }
+
+        /// Change since `previous`. A series that is new, or whose value went
+        /// down because the process restarted, counts from zero.
+        pub fn diff(&self, previous: &MetricsSnapshot) -> MetricsDelta {
+            let deltas = self
+                .values
+                .iter()
+                .map(|(series, &current)| {
+                    let delta = match previous.values.get(series) {
+                        Some(&before) if current >= before => current - before,
+                        _ => current,
+                    };
+                    (series.clone(), delta)
+                })
+                .collect();
+
+            MetricsDelta {
+                interval: self.taken_at.saturating_duration_since(previous.taken_at),
+                deltas,
+            }
+        }
+    }
+
+    impl MetricsDelta {
+        /// Per-second rate of `series`; `None` if it is unknown or the
+        /// snapshots were taken at the same instant
+        pub fn rate(&self, series: &str) -> Option<f64> {
+            let secs = self.interval.as_secs_f64();
+            if secs == 0.0 {
+                return None;
+            }
+            self.deltas.get(series).map(|delta| delta / secs)
+        }
+
+        /// Per-second rate of every series
+        pub fn rates(&self) -> BTreeMap<String, f64> {
+            self.deltas
+                .keys()
+                .filter_map(|series| self.rate(series).map(|rate| (series.clone(), rate)))
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_latency_is_split_by_status_code() {
+            let method = "/core_engine.CoreEngineService/LatencyTest";
+            record_request_latency(method, tonic::Code::Ok, 0.004);
+            record_request_latency(method, tonic::Code::Ok, 0.020);
+            record_request_latency(method, tonic::Code::Unavailable, 1.5);
+
+            let histogram = request_latency_histogram();
+            let ok = histogram.with_label_values(&[method, "Ok"]);
+            let failed = histogram.with_label_values(&[method, "Unavailable"]);
+            assert_eq!(ok.get_sample_count(), 2);
+            assert_eq!(failed.get_sample_count(), 1);
+            assert!((failed.get_sample_sum() - 1.5).abs() < f64::EPSILON);
+
+            let exported = export_metrics();
+            assert!(exported.contains(&format!("method=\"{}\"", method)));
+            assert!(exported.contains("code=\"Unavailable\""));
+        }
+
+        #[test]
+        fn test_component_named_overall_keeps_combined_score() {
+            use crate::health::{ComponentScore, HealthStatus};
+
+            record_health(&HealthReport {
+                score: 90,
+                status: HealthStatus::Healthy,
+                breakdown: vec![ComponentScore {
+                    name: OVERALL_HEALTH_COMPONENT.to_string(),
+                    score: 10.0,
+                    weight: 1.0,
+                }],
+            });
+
+            let exported = export_metrics();
+            assert!(exported.contains("health_score{component=\"component:overall\"} 10\n"));
+            assert!(!exported.contains("health_score{component=\"overall\"} 10\n"));
+        }
+
+        fn local_registry() -> (Registry, Counter, HistogramVec) {
+            let registry = Registry::new();
+            let counter = Counter::new("requests_total", "requests").unwrap();
+            let histogram = HistogramVec::new(HistogramOpts::new("latency_seconds", "latency"), &["code"]).unwrap();
+            registry.register(Box::new(counter.clone())).unwrap();
+            registry.register(Box::new(histogram.clone())).unwrap();
+            (registry, counter, histogram)
+        }
+
+        #[test]
+        fn test_snapshot_diff_gives_rate_over_interval() {
+            let (registry, counter, histogram) = local_registry();
+            counter.inc_by(5.0);
+            let before = MetricsSnapshot::from_registry(&registry);
+
+            counter.inc_by(30.0);
+            histogram.with_label_values(&["Ok"]).observe(0.25);
+            histogram.with_label_values(&["Ok"]).observe(0.75);
+            let mut after = MetricsSnapshot::from_registry(&registry);
+            after.taken_at = before.taken_at + Duration::from_secs(10);
+
+            let delta = after.diff(&before);
+            assert_eq!(delta.interval, Duration::from_secs(10));
+            assert_eq!(delta.deltas["requests_total"], 30.0);
+            assert_eq!(delta.rate("requests_total"), Some(3.0));
+            assert_eq!(delta.deltas["latency_seconds_count{code=\"Ok\"}"], 2.0);
+            assert_eq!(delta.rate("latency_seconds_sum{code=\"Ok\"}"), Some(0.1));
+            assert_eq!(delta.rates().len(), delta.deltas.len());
+        }
+
+        #[test]
+        fn test_diff_handles_reset_and_zero_interval() {
+            let (registry, counter, _) = local_registry();
+            counter.inc_by(100.0);
+            let mut before = MetricsSnapshot::from_registry(&registry);
+            before.values.insert("requests_total".to_string(), 500.0);
+
+            let mut after = MetricsSnapshot::from_registry(&registry);
+            after.taken_at = before.taken_at;
+
+            let delta = after.diff(&before);
+            assert_eq!(delta.deltas["requests_total"], 100.0);
+            assert_eq!(delta.rate("requests_total"), None);
+            assert_eq!(delta.rate("missing_total"), None);
+        }
+    }
 }
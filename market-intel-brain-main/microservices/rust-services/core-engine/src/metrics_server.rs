@@ -0,0 +1,125 @@
+//! HTTP endpoint for Prometheus scraping and Kubernetes probes
+//!
+//! Serves the metrics endpoint configured in `[metrics]`, plus `/healthz`
+//! (liveness: the process is up and answering) and `/readyz` (readiness: all
+//! startup dependencies in [`Readiness`] have initialized). Each scrape
+//! re-evaluates [`EngineHealth`] so the health gauge is current.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Request, Response, Server, StatusCode};
+use tracing::info;
+
+use crate::health::{EngineHealth, Readiness};
+use crate::metrics::prometheus::{export_metrics, record_health};
+
+/// Liveness probe path
+pub const LIVENESS_PATH: &str = "/healthz";
+
+/// Readiness probe path
+pub const READINESS_PATH: &str = "/readyz";
+
+/// Serve metrics and probes on `addr` until the task is dropped
+pub async fn serve(addr: SocketAddr, metrics_path: String, readiness: Arc<Readiness>) -> hyper::Result<()> {
+    let metrics_path = Arc::new(metrics_path);
+    let health = Arc::new(EngineHealth::new(readiness));
+    let make_service = make_service_fn(move |_conn| {
+        let metrics_path = metrics_path.clone();
+        let health = health.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let response = route(request.uri().path(), &metrics_path, &health);
+                async move { Ok::<_, Infallible>(response) }
+            }))
+        }
+    });
+
+    info!("Metrics and probes listening on {}", addr);
+    Server::bind(&addr).serve(make_service).await
+}
+
+fn route(path: &str, metrics_path: &str, health: &EngineHealth) -> Response<Body> {
+    let readiness = health.readiness();
+    match path {
+        LIVENESS_PATH => text(StatusCode::OK, "ok".to_string()),
+        READINESS_PATH if readiness.is_ready() => text(StatusCode::OK, "ready".to_string()),
+        READINESS_PATH => text(
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("waiting for: {}", readiness.pending().join(", ")),
+        ),
+        _ if path == metrics_path => {
+            record_health(&health.evaluate());
+            text(StatusCode::OK, export_metrics())
+        }
+        _ => text(StatusCode::NOT_FOUND, "not found".to_string()),
+    }
+}
+
+fn text(status: StatusCode, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, header::HeaderValue::from_static("text/plain; charset=utf-8"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(response: Response<Body>) -> String {
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn health(components: &[&str]) -> (Arc<Readiness>, EngineHealth) {
+        let readiness = Arc::new(Readiness::new(components.iter().copied()));
+        (readiness.clone(), EngineHealth::new(readiness))
+    }
+
+    #[tokio::test]
+    async fn test_readiness_flips_after_dependencies_initialize() {
+        let (readiness, health) = health(&["analytics", "vector_store"]);
+
+        let response = route(READINESS_PATH, "/metrics", &health);
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body(response).await, "waiting for: analytics, vector_store");
+
+        readiness.mark_ready("analytics");
+        assert_eq!(route(READINESS_PATH, "/metrics", &health).status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        readiness.mark_ready("vector_store");
+        assert_eq!(route(READINESS_PATH, "/metrics", &health).status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_liveness_ignores_readiness() {
+        let (_, health) = health(&["analytics"]);
+        assert_eq!(route(LIVENESS_PATH, "/metrics", &health).status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_metrics_and_unknown_paths() {
+        let (_, health) = health(&[]);
+        assert_eq!(route("/metrics", "/metrics", &health).status(), StatusCode::OK);
+        assert_eq!(route("/nope", "/metrics", &health).status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_scrape_publishes_component_health() {
+        let (readiness, health) = health(&["scrape_analytics", "scrape_vector_store"]);
+        readiness.mark_ready("scrape_analytics");
+
+        let exported = body(route("/metrics", "/metrics", &health)).await;
+        assert!(exported.contains("health_score{component=\"scrape_analytics\"} 100\n"));
+        assert!(exported.contains("health_score{component=\"scrape_vector_store\"} 0\n"));
+
+        readiness.mark_ready("scrape_vector_store");
+        let exported = body(route("/metrics", "/metrics", &health)).await;
+        assert!(exported.contains("health_score{component=\"scrape_vector_store\"} 100\n"));
+    }
+}
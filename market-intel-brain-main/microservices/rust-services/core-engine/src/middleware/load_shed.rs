@@ -0,0 +1,202 @@
+//! Concurrency limiting with load shedding
+//!
+//! Requests beyond the in-flight limit are rejected immediately with
+//! `RESOURCE_EXHAUSTED` rather than queued, so an overloaded server sheds
+//! work instead of accumulating memory. The permit is held until the
+//! handler returns its response.
+
+use futures::future::BoxFuture;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::Semaphore;
+use tonic::body::BoxBody;
+use tonic::codegen::http::{Request, Response};
+use tonic::Status;
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::analytics::{self, AnalyticsEvent, AnalyticsEventType};
+
+type ShedHook = Arc<dyn Fn(AnalyticsEvent) + Send + Sync>;
+
+/// Layer producing [`LoadShedService`]; all services built from one layer
+/// share its limit
+#[derive(Clone)]
+pub struct LoadShedLayer {
+    max_in_flight: usize,
+    permits: Arc<Semaphore>,
+    on_shed: ShedHook,
+}
+
+impl LoadShedLayer {
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight,
+            permits: Arc::new(Semaphore::new(max_in_flight)),
+            on_shed: Arc::new(analytics::emit),
+        }
+    }
+
+    /// Send `RateLimitTriggered` events somewhere other than [`analytics::emit`].
+    pub fn on_shed<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(AnalyticsEvent) + Send + Sync + 'static,
+    {
+        self.on_shed = Arc::new(hook);
+        self
+    }
+
+    /// Requests currently being handled.
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.permits.available_permits()
+    }
+}
+
+impl<S> Layer<S> for LoadShedLayer {
+    type Service = LoadShedService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LoadShedService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// Service wrapper rejecting requests over the limit
+#[derive(Clone)]
+pub struct LoadShedService<S> {
+    inner: S,
+    config: LoadShedLayer,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for LoadShedService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let permit = match self.config.permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let path = request.uri().path().to_string();
+                let limit = self.config.max_in_flight;
+                warn!("Shedding {}: {} requests already in flight", path, limit);
+                (self.config.on_shed)(
+                    AnalyticsEvent::new(AnalyticsEventType::RateLimitTriggered)
+                        .with_field("request_type", path)
+                        .with_field("limit_type", "max_in_flight")
+                        .with_field("limit", limit as u64),
+                );
+                let response = Status::resource_exhausted(format!(
+                    "server is at its limit of {} concurrent requests",
+                    limit
+                ))
+                .to_http();
+                return Box::pin(async move { Ok(response) });
+            }
+        };
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let result = future.await;
+            drop(permit);
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::sync::Mutex;
+    use tonic::Code;
+
+    /// Handler that blocks until the test opens the gate
+    #[derive(Clone)]
+    struct Gated {
+        gate: Arc<Semaphore>,
+    }
+
+    impl Service<Request<()>> for Gated {
+        type Response = Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<'static, Result<Response<BoxBody>, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: Request<()>) -> Self::Future {
+            let gate = self.gate.clone();
+            Box::pin(async move {
+                let _pass = gate.acquire().await.unwrap();
+                Ok(Response::new(tonic::body::empty_body()))
+            })
+        }
+    }
+
+    fn grpc_code(response: &Response<BoxBody>) -> Code {
+        response
+            .headers()
+            .get("grpc-status")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i32>().ok())
+            .map(Code::from)
+            .unwrap_or(Code::Ok)
+    }
+
+    fn request() -> Request<()> {
+        Request::builder()
+            .uri("/market_intel.core_engine.v1.CoreEngineService/GetStatus")
+            .body(())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_over_limit_is_shed() {
+        let gate = Arc::new(Semaphore::new(0));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = events.clone();
+        let layer = LoadShedLayer::new(2).on_shed(move |e| seen.lock().unwrap().push(e));
+        let mut svc = layer.layer(Gated { gate: gate.clone() });
+
+        let first = tokio::spawn(svc.call(request()));
+        let second = tokio::spawn(svc.call(request()));
+        assert_eq!(layer.in_flight(), 2);
+
+        let shed = svc.call(request()).await.unwrap();
+        assert_eq!(grpc_code(&shed), Code::ResourceExhausted);
+        {
+            let events = events.lock().unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].event_type, AnalyticsEventType::RateLimitTriggered);
+        }
+
+        gate.add_permits(2);
+        assert_eq!(grpc_code(&first.await.unwrap().unwrap()), Code::Ok);
+        assert_eq!(grpc_code(&second.await.unwrap().unwrap()), Code::Ok);
+        assert_eq!(layer.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_is_returned_after_response() {
+        let gate = Arc::new(Semaphore::new(10));
+        let layer = LoadShedLayer::new(1).on_shed(|_| {});
+        let mut svc = layer.layer(Gated { gate });
+
+        for _ in 0..3 {
+            let response = svc.call(request()).await.unwrap();
+            assert_eq!(grpc_code(&response), Code::Ok);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Tower middleware applied to the gRPC server
+
+pub mod load_shed;
+pub mod request_log;
+pub mod timeout;
+
+pub use load_shed::LoadShedLayer;
+pub use request_log::RequestLogLayer;
+pub use timeout::{RequestTimeoutLayer, DEFAULT_REQUEST_TIMEOUT};
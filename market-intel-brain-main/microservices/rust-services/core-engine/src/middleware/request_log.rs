@@ -0,0 +1,439 @@
+//! Structured per-request logging
+//!
+//! Emits one event per gRPC call with the method path, gRPC status code,
+//! latency and the OpenTelemetry trace id propagated by the caller. The
+//! same latency is recorded in the per-method, per-status histogram; paths
+//! that aren't `CoreEngineService` methods are recorded as `"unknown"` so
+//! callers can't create arbitrary series.
+
+use futures::future::BoxFuture;
+use opentelemetry::global;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::{TraceContextExt, TraceId};
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tonic::codegen::http::{HeaderMap, Request, Response};
+use tower::{Layer, Service};
+use tracing::Level;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::metrics::prometheus as metrics;
+
+/// Paths skipped by default so liveness probes don't flood the logs
+const DEFAULT_SKIP_PATHS: &[&str] = &["/grpc.health.v1.Health/", "/HealthCheck"];
+
+/// gRPC path prefix of `CoreEngineService` (see `core_engine.proto`)
+const SERVICE_PREFIX: &str = "/market_intel.core_engine.v1.CoreEngineService/";
+
+/// `CoreEngineService` rpcs, in `core_engine.proto` order
+const SERVICE_METHODS: &[&str] = &[
+    "HealthCheck",
+    "ProcessMessage",
+    "ProcessBatchMessages",
+    "ProcessStreamMessages",
+    "CreateAgent",
+    "GetAgent",
+    "UpdateAgent",
+    "DeleteAgent",
+    "ListAgents",
+    "StartAgent",
+    "StopAgent",
+    "RestartAgent",
+    "GetConfiguration",
+    "UpdateConfiguration",
+    "ResetConfiguration",
+    "GetMetrics",
+    "GetPerformanceMetrics",
+    "GetResourceUsage",
+    "IngestData",
+    "IngestBatchData",
+    "GetDataIngestionStatus",
+    "RunAnalysis",
+    "GetAnalysisResults",
+    "GenerateSignals",
+    "GetSignals",
+    "CreateVector",
+    "SearchVectors",
+    "UpdateVector",
+    "DeleteVector",
+    "GetFromCache",
+    "SetInCache",
+    "DeleteFromCache",
+    "ClearCache",
+    "SubscribeToEvents",
+    "PublishEvent",
+    "GetEventHistory",
+    "CreateTask",
+    "GetTask",
+    "UpdateTask",
+    "CancelTask",
+    "ListTasks",
+    "CreatePipeline",
+    "GetPipeline",
+    "UpdatePipeline",
+    "DeletePipeline",
+    "ListPipelines",
+    "ExecutePipeline",
+];
+
+/// Histogram label for the `method` dimension: the path for service
+/// methods, `"unknown"` for anything else
+fn metric_method(path: &str) -> &str {
+    match path.strip_prefix(SERVICE_PREFIX) {
+        Some(method) if SERVICE_METHODS.contains(&method) => path,
+        _ => "unknown",
+    }
+}
+
+/// Layer producing [`RequestLogService`]
+#[derive(Debug, Clone)]
+pub struct RequestLogLayer {
+    level: Level,
+    skip_paths: Vec<String>,
+}
+
+impl RequestLogLayer {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            skip_paths: DEFAULT_SKIP_PATHS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    /// Parse a level name such as `"info"`; unknown names fall back to INFO.
+    pub fn from_level_name(name: &str) -> Self {
+        Self::new(Level::from_str(name).unwrap_or(Level::INFO))
+    }
+
+    /// Don't log requests whose path starts or ends with `pattern`.
+    pub fn skip_path(mut self, pattern: impl Into<String>) -> Self {
+        self.skip_paths.push(pattern.into());
+        self
+    }
+
+    /// Log every request, health checks included.
+    pub fn log_all(mut self) -> Self {
+        self.skip_paths.clear();
+        self
+    }
+}
+
+impl Default for RequestLogLayer {
+    fn default() -> Self {
+        Self::new(Level::INFO)
+    }
+}
+
+impl<S> Layer<S> for RequestLogLayer {
+    type Service = RequestLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestLogService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// Service wrapper that logs each request once its response headers are ready
+#[derive(Debug, Clone)]
+pub struct RequestLogService<S> {
+    inner: S,
+    config: RequestLogLayer,
+}
+
+impl<S> RequestLogService<S> {
+    fn skipped(&self, path: &str) -> bool {
+        self.config
+            .skip_paths
+            .iter()
+            .any(|p| path.starts_with(p.as_str()) || path.ends_with(p.as_str()))
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestLogService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: std::fmt::Display,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let path = request.uri().path().to_string();
+        if self.skipped(&path) {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let trace_id = trace_id_for(request.headers());
+        let level = self.config.level;
+        let started = Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let result = future.await;
+            let elapsed = started.elapsed().as_secs_f64();
+            let duration_ms = elapsed * 1000.0;
+            match &result {
+                Ok(response) => {
+                    // Errors come back trailers-only with the code in the
+                    // headers; a missing header means the call succeeded.
+                    let code = response
+                        .headers()
+                        .get("grpc-status")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.parse::<i32>().ok())
+                        .unwrap_or(0);
+                    log_request(level, &path, code, duration_ms, &trace_id);
+                    metrics::record_request_latency(metric_method(&path), tonic::Code::from_i32(code), elapsed);
+                }
+                Err(e) => {
+                    // No gRPC status reached the caller
+                    metrics::record_request_latency(metric_method(&path), tonic::Code::Unknown, elapsed);
+                    tracing::error!(
+                        grpc.method = %path,
+                        duration_ms,
+                        trace_id = %trace_id,
+                        error = %e,
+                        "gRPC request failed"
+                    );
+                }
+            }
+            result
+        })
+    }
+}
+
+fn log_request(level: Level, path: &str, code: i32, duration_ms: f64, trace_id: &str) {
+    macro_rules! emit {
+        ($lvl:expr) => {
+            tracing::event!(
+                $lvl,
+                grpc.method = %path,
+                grpc.status = code,
+                duration_ms,
+                trace_id = %trace_id,
+                "gRPC request"
+            )
+        };
+    }
+    match level {
+        Level::TRACE => emit!(Level::TRACE),
+        Level::DEBUG => emit!(Level::DEBUG),
+        Level::INFO => emit!(Level::INFO),
+        Level::WARN => emit!(Level::WARN),
+        Level::ERROR => emit!(Level::ERROR),
+    }
+}
+
+/// Trace id from the caller's `traceparent`, or from the current span.
+fn trace_id_for(headers: &HeaderMap) -> String {
+    let parent = global::get_text_map_propagator(|p| p.extract(&HeaderExtractor(headers)));
+    let mut trace_id = parent.span().span_context().trace_id();
+    if trace_id == TraceId::INVALID {
+        trace_id = tracing::Span::current()
+            .context()
+            .span()
+            .span_context()
+            .trace_id();
+    }
+    if trace_id == TraceId::INVALID {
+        String::new()
+    } else {
+        trace_id.to_string()
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::future::{ready, Ready};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+
+    /// Collects `grpc.method` / `grpc.status` of every event
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<(String, i64)>>>);
+
+    struct Fields(String, i64);
+
+    impl Visit for Fields {
+        fn record_i64(&mut self, field: &Field, value: i64) {
+            if field.name() == "grpc.status" {
+                self.1 = value;
+            }
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "grpc.method" {
+                self.0 = format!("{:?}", value);
+            }
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Capture {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: LayerContext<'_, S>) {
+            let mut fields = Fields(String::new(), -1);
+            event.record(&mut fields);
+            if !fields.0.is_empty() {
+                self.0.lock().unwrap().push((fields.0, fields.1));
+            }
+        }
+    }
+
+    /// Answers every request, failing calls to `/Fail` with NOT_FOUND (5)
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Service<Request<()>> for Echo {
+        type Response = Response<()>;
+        type Error = Infallible;
+        type Future = Ready<Result<Response<()>, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<()>) -> Self::Future {
+            let mut response = Response::new(());
+            if request.uri().path().ends_with("/Fail") {
+                response
+                    .headers_mut()
+                    .insert("grpc-status", "5".parse().unwrap());
+            }
+            ready(Ok(response))
+        }
+    }
+
+    fn request(path: &str) -> Request<()> {
+        Request::builder().uri(path).body(()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_logs_one_entry_per_request() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut svc = RequestLogLayer::default().layer(Echo);
+        svc.call(request(
+            "/market_intel.core_engine.v1.CoreEngineService/GetStatus",
+        ))
+        .await
+        .unwrap();
+        svc.call(request(
+            "/market_intel.core_engine.v1.CoreEngineService/Fail",
+        ))
+        .await
+        .unwrap();
+        svc.call(request(
+            "/market_intel.core_engine.v1.CoreEngineService/HealthCheck",
+        ))
+        .await
+        .unwrap();
+
+        let entries = capture.0.lock().unwrap().clone();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "/market_intel.core_engine.v1.CoreEngineService/GetStatus".to_string(),
+                    0
+                ),
+                (
+                    "/market_intel.core_engine.v1.CoreEngineService/Fail".to_string(),
+                    5
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_log_all_includes_health_checks() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut svc = RequestLogLayer::default().log_all().layer(Echo);
+        svc.call(request("/grpc.health.v1.Health/Check"))
+            .await
+            .unwrap();
+
+        assert_eq!(capture.0.lock().unwrap().len(), 1);
+    }
+
+    /// Fails every call at the transport level
+    #[derive(Clone)]
+    struct Broken;
+
+    impl Service<Request<()>> for Broken {
+        type Response = Response<()>;
+        type Error = &'static str;
+        type Future = Ready<Result<Response<()>, &'static str>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), &'static str>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: Request<()>) -> Self::Future {
+            ready(Err("connection reset"))
+        }
+    }
+
+    #[test]
+    fn test_unknown_paths_share_one_metric_label() {
+        let known = "/market_intel.core_engine.v1.CoreEngineService/GetSignals";
+        assert_eq!(metric_method(known), known);
+        assert_eq!(metric_method("/market_intel.core_engine.v1.CoreEngineService/Nope"), "unknown");
+        assert_eq!(metric_method("/random/a8f3c2"), "unknown");
+        assert_eq!(metric_method("/market_intel.core_engine.v1.CoreEngineService/"), "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors_record_latency() {
+        let (before, failed_before) = metrics::request_counts();
+
+        let mut svc = RequestLogLayer::default().layer(Broken);
+        assert!(svc.call(request("/random/path")).await.is_err());
+
+        let (after, failed_after) = metrics::request_counts();
+        assert!(after > before);
+        assert!(failed_after > failed_before);
+    }
+
+    #[test]
+    fn test_trace_id_from_traceparent() {
+        global::set_text_map_propagator(
+            opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(trace_id_for(&headers), "4bf92f3577b34da6a3ce929d0e0e4736");
+    }
+}
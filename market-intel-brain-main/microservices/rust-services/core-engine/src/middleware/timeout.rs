@@ -0,0 +1,193 @@
+//! Server-side deadline for unary RPCs
+//!
+//! A handler that runs past the deadline is dropped and the caller receives
+//! `DEADLINE_EXCEEDED`. Streaming RPCs are long-lived by design and are
+//! passed through untouched.
+
+use futures::future::BoxFuture;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tonic::body::BoxBody;
+use tonic::codegen::http::{Request, Response};
+use tonic::Status;
+use tower::{Layer, Service};
+use tracing::warn;
+
+/// Deadline applied when none is configured
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Streaming methods of `CoreEngineService`, matched on the method name
+const STREAMING_METHODS: &[&str] = &[
+    "ProcessStreamMessages",
+    "SubscribeToEvents",
+    "ExecutePipeline",
+];
+
+/// Layer producing [`RequestTimeoutService`]
+#[derive(Debug, Clone)]
+pub struct RequestTimeoutLayer {
+    timeout: Duration,
+    exempt_methods: Vec<String>,
+}
+
+impl RequestTimeoutLayer {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            exempt_methods: STREAMING_METHODS.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    /// Exclude another method (by name, e.g. `"WatchPrices"`) from the deadline.
+    pub fn exempt(mut self, method: impl Into<String>) -> Self {
+        self.exempt_methods.push(method.into());
+        self
+    }
+}
+
+impl Default for RequestTimeoutLayer {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUEST_TIMEOUT)
+    }
+}
+
+impl<S> Layer<S> for RequestTimeoutLayer {
+    type Service = RequestTimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestTimeoutService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// Service wrapper enforcing the deadline
+#[derive(Debug, Clone)]
+pub struct RequestTimeoutService<S> {
+    inner: S,
+    config: RequestTimeoutLayer,
+}
+
+impl<S> RequestTimeoutService<S> {
+    fn is_exempt(&self, path: &str) -> bool {
+        let method = path.rsplit('/').next().unwrap_or_default();
+        self.config.exempt_methods.iter().any(|m| m == method)
+    }
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RequestTimeoutService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        if self.is_exempt(request.uri().path()) {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let path = request.uri().path().to_string();
+        let timeout = self.config.timeout;
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            match tokio::time::timeout(timeout, future).await {
+                Ok(result) => result,
+                Err(_) => {
+                    warn!("{} exceeded the {:?} deadline", path, timeout);
+                    Ok(Status::deadline_exceeded(format!(
+                        "request exceeded the {}ms server deadline",
+                        timeout.as_millis()
+                    ))
+                    .to_http())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use tonic::Code;
+
+    /// Handler that takes `delay` to answer
+    #[derive(Clone)]
+    struct Slow {
+        delay: Duration,
+    }
+
+    impl Service<Request<()>> for Slow {
+        type Response = Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<'static, Result<Response<BoxBody>, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: Request<()>) -> Self::Future {
+            let delay = self.delay;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                Ok(Response::new(tonic::body::empty_body()))
+            })
+        }
+    }
+
+    fn grpc_code(response: &Response<BoxBody>) -> Code {
+        response
+            .headers()
+            .get("grpc-status")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i32>().ok())
+            .map(Code::from)
+            .unwrap_or(Code::Ok)
+    }
+
+    fn request(method: &str) -> Request<()> {
+        Request::builder()
+            .uri(format!(
+                "/market_intel.core_engine.v1.CoreEngineService/{}",
+                method
+            ))
+            .body(())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_slow_unary_handler_gets_deadline_exceeded() {
+        let mut svc = RequestTimeoutLayer::new(Duration::from_millis(20)).layer(Slow {
+            delay: Duration::from_millis(500),
+        });
+        let response = svc.call(request("GetStatus")).await.unwrap();
+        assert_eq!(grpc_code(&response), Code::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_fast_handler_is_unaffected() {
+        let mut svc = RequestTimeoutLayer::new(Duration::from_millis(500)).layer(Slow {
+            delay: Duration::from_millis(1),
+        });
+        let response = svc.call(request("GetStatus")).await.unwrap();
+        assert_eq!(grpc_code(&response), Code::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_methods_are_exempt() {
+        let mut svc = RequestTimeoutLayer::new(Duration::from_millis(20)).layer(Slow {
+            delay: Duration::from_millis(100),
+        });
+        let response = svc.call(request("SubscribeToEvents")).await.unwrap();
+        assert_eq!(grpc_code(&response), Code::Ok);
+    }
+}
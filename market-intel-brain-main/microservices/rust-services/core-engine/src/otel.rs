@@ -3,15 +3,77 @@ use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::{RandomIdGenerator, Sampler};
 use opentelemetry_sdk::{trace as sdktrace, Resource};
 use opentelemetry::KeyValue;
-use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, fmt};
+use tracing::{info, warn};
+use std::sync::OnceLock;
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry, fmt};
 use tracing_opentelemetry;
 use opentelemetry_jaeger;
 
-pub fn init_telemetry(service_name: &str, service_version: &str) -> anyhow::Result<()> {
+/// Handle to the installed log filter, used to change the level at runtime.
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the log subscriber and the Jaeger trace exporter.
+///
+/// Logs are filtered at `log_level` unless `RUST_LOG` is set, which takes
+/// precedence. If the exporter cannot be set up, logging still comes up and spans go to
+/// a no-op tracer, unless `required` is set, in which case the error is
+/// returned before anything is installed.
+pub fn init_telemetry(
+    service_name: &str,
+    service_version: &str,
+    log_level: &str,
+    required: bool,
+) -> anyhow::Result<()> {
     let jaeger_endpoint = std::env::var("JAEGER_ENDPOINT")
         .unwrap_or_else(|_| "http://localhost:14268/api/traces".to_string());
+    init_with_endpoint(service_name, service_version, &jaeger_endpoint, log_level, required)
+}
+
+fn init_with_endpoint(
+    service_name: &str,
+    service_version: &str,
+    jaeger_endpoint: &str,
+    log_level: &str,
+    required: bool,
+) -> anyhow::Result<()> {
+    let (tracer, exporter_error) = match build_tracer(service_name, service_version, jaeger_endpoint) {
+        Ok(tracer) => (Some(tracer), None),
+        Err(e) if required => return Err(e),
+        Err(e) => (None, Some(e)),
+    };
+
+    let rust_log = std::env::var(EnvFilter::DEFAULT_ENV).ok();
+    let (filter, filter_handle) = reload::Layer::new(initial_filter(rust_log.as_deref(), log_level));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().json())
+        .with(tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)))
+        .try_init()?;
+    let _ = LOG_FILTER.set(filter_handle);
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    match exporter_error {
+        None => info!("OpenTelemetry initialized"),
+        Some(e) => warn!("Trace export disabled, continuing without it: {}", e),
+    }
+    Ok(())
+}
+
+/// `RUST_LOG` if set and valid, else the configured level, else `info`.
+fn initial_filter(rust_log: Option<&str>, log_level: &str) -> EnvFilter {
+    rust_log
+        .and_then(|directives| EnvFilter::try_new(directives).ok())
+        .or_else(|| EnvFilter::try_new(log_level).ok())
+        .unwrap_or_else(|| EnvFilter::new("info"))
+}
 
+fn build_tracer(
+    service_name: &str,
+    service_version: &str,
+    jaeger_endpoint: &str,
+) -> anyhow::Result<sdktrace::Tracer> {
     let tracer = opentelemetry_jaeger::new_collector_pipeline()
         .with_endpoint(jaeger_endpoint)
         .with_service_name(service_name)
@@ -28,23 +90,49 @@ pub fn init_telemetry(service_name: &str, service_version: &str) -> anyhow::Resu
                 ]))
         )
         .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+    Ok(tracer)
+}
 
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info".into()),
-        )
-        .with(tracing_subscriber::fmt::layer().json())
-        .with(tracing_opentelemetry::layer().with_tracer(tracer))
-        .init();
-
-    global::set_text_map_propagator(TraceContextPropagator::new());
-
-    info!("OpenTelemetry initialized");
+/// Replace the active log filter, e.g. `"debug"` or `"core_engine=trace"`.
+pub fn set_log_level(level: &str) -> anyhow::Result<()> {
+    let handle = LOG_FILTER
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("telemetry is not initialised"))?;
+    handle.reload(EnvFilter::try_new(level)?)?;
+    info!("Log level set to {}", level);
     Ok(())
 }
 
 pub fn shutdown_telemetry() {
     global::shutdown_tracer_provider();
     tracing::info!("OpenTelemetry shutdown");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CoreEngineConfig;
+    use crate::core_engine_service::CoreEngineServiceImpl;
+
+    const BAD_ENDPOINT: &str = "not a uri";
+
+    #[tokio::test]
+    async fn test_exporter_failure_is_fatal_only_when_required() {
+        assert!(build_tracer("core-engine-test", "0.0.0", BAD_ENDPOINT).is_err());
+        assert!(init_with_endpoint("core-engine-test", "0.0.0", BAD_ENDPOINT, "info", true).is_err());
+
+        init_with_endpoint("core-engine-test", "0.0.0", BAD_ENDPOINT, "info", false)
+            .expect("exporter failure should not abort startup");
+        assert!(set_log_level("debug").is_ok());
+        let ingestion = std::sync::Arc::new(crate::data_ingestion::DataIngestionService::default());
+        assert!(CoreEngineServiceImpl::new(CoreEngineConfig::default(), ingestion).await.is_ok());
+    }
+
+    #[test]
+    fn test_initial_filter_uses_configured_level() {
+        assert_eq!(initial_filter(None, "warn").to_string(), "warn");
+        assert_eq!(initial_filter(Some("core_engine=trace"), "warn").to_string(), "core_engine=trace");
+        assert_eq!(initial_filter(Some("core_engine=loud"), "debug").to_string(), "debug");
+        assert_eq!(initial_filter(None, "core_engine=loud").to_string(), "info");
+    }
+}
@@ -0,0 +1,63 @@
+//! Helpers for the common `Response` wrapper
+//!
+//! The v1 protos replaced `StandardResponse`/`ResponseStatus` with
+//! `common.v1.Response { success, message, code, ... }`; these constructors
+//! keep handlers from filling it in by hand.
+
+use std::collections::HashMap;
+use tonic::{Code, Status};
+
+use crate::core_engine_service::proto::common::Response;
+
+/// `code` value used for successful responses
+pub const SUCCESS_CODE: &str = "OK";
+
+impl Response {
+    /// Successful response carrying `message`.
+    pub fn success(message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            message: message.into(),
+            code: SUCCESS_CODE.to_string(),
+            data: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Failed response; `code` is rendered with its gRPC name (e.g. `NotFound`).
+    pub fn failure(code: Code, message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            code: format!("{:?}", code),
+            data: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Failed response mirroring a `Status`.
+    pub fn from_status(status: &Status) -> Self {
+        Self::failure(status.code(), status.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success() {
+        let response = Response::success("agent registered");
+        assert!(response.success);
+        assert_eq!(response.code, SUCCESS_CODE);
+        assert_eq!(response.message, "agent registered");
+    }
+
+    #[test]
+    fn test_failure_from_status() {
+        let response = Response::from_status(&Status::resource_exhausted("slow down"));
+        assert!(!response.success);
+        assert_eq!(response.code, "ResourceExhausted");
+        assert_eq!(response.message, "slow down");
+    }
+}
@@ -6,6 +6,8 @@
 use std::path::PathBuf;
 use tonic::transport::{Certificate, Identity, ServerTlsConfig};
 
+use crate::errors::{EngineError, EngineResult};
+
 /// TLS configuration loaded from environment variables.
 pub struct TlsConfig {
     /// Path to the PEM-encoded server certificate
@@ -41,23 +43,23 @@ impl TlsConfig {
     }
 
     /// Validate that required files exist when TLS is enabled.
-    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn validate(&self) -> EngineResult<()> {
         if !self.tls_enabled {
             return Ok(());
         }
 
-        let cert = self.cert_path.as_ref().ok_or("TLS_CERT_PATH is not set")?;
-        let key = self.key_path.as_ref().ok_or("TLS_KEY_PATH is not set")?;
+        let cert = self.cert_path.as_ref().ok_or_else(|| missing("TLS_CERT_PATH"))?;
+        let key = self.key_path.as_ref().ok_or_else(|| missing("TLS_KEY_PATH"))?;
 
         if !cert.exists() {
-            return Err(format!("TLS cert not found: {}", cert.display()).into());
+            return Err(EngineError::Tls(format!("TLS cert not found: {}", cert.display())));
         }
         if !key.exists() {
-            return Err(format!("TLS key not found: {}", key.display()).into());
+            return Err(EngineError::Tls(format!("TLS key not found: {}", key.display())));
         }
         if let Some(ca) = &self.ca_cert_path {
             if !ca.exists() {
-                return Err(format!("CA cert not found: {}", ca.display()).into());
+                return Err(EngineError::Tls(format!("CA cert not found: {}", ca.display())));
             }
         }
 
@@ -70,15 +72,15 @@ impl TlsConfig {
     /// plain TLS otherwise.  Returns `None` when `TLS_ENABLED` is false.
     pub fn create_server_tls_config(
         &self,
-    ) -> Result<Option<ServerTlsConfig>, Box<dyn std::error::Error>> {
+    ) -> EngineResult<Option<ServerTlsConfig>> {
         if !self.tls_enabled {
             return Ok(None);
         }
 
         let cert_pem =
-            std::fs::read(self.cert_path.as_ref().ok_or("TLS_CERT_PATH is not set")?)?;
+            std::fs::read(self.cert_path.as_ref().ok_or_else(|| missing("TLS_CERT_PATH"))?)?;
         let key_pem =
-            std::fs::read(self.key_path.as_ref().ok_or("TLS_KEY_PATH is not set")?)?;
+            std::fs::read(self.key_path.as_ref().ok_or_else(|| missing("TLS_KEY_PATH"))?)?;
 
         let identity = Identity::from_pem(cert_pem, key_pem);
         let mut tls = ServerTlsConfig::new().identity(identity);
@@ -93,4 +95,8 @@ impl TlsConfig {
 
         Ok(Some(tls))
     }
-}
\ No newline at end of file
+}
+
+fn missing(var: &str) -> EngineError {
+    EngineError::Tls(format!("{} is not set", var))
+}
@@ -0,0 +1,417 @@
+//! Shared utilities
+//!
+//! Small building blocks reused across subsystems that would otherwise each
+//! grow their own copy.
+
+use std::future::Future;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use rand::Rng;
+use tracing::debug;
+
+/// Exponential backoff with an upper bound and optional jitter
+///
+/// The nominal delay for attempt `n` (starting at 0) is
+/// `base * multiplier^n`, capped at `max`. Jitter subtracts a random fraction
+/// of up to `jitter` from that delay, so a jittered delay never exceeds `max`.
+///
+/// ```ignore
+/// let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(5)).with_jitter(0.2);
+/// let data = backoff.retry_async(5, || source.fetch()).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    multiplier: f64,
+    jitter: f64,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Doubling backoff from `base` up to `max`, without jitter
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            multiplier: 2.0,
+            jitter: 0.0,
+            attempt: 0,
+        }
+    }
+
+    /// Growth factor between attempts; values below 1.0 are treated as 1.0
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = if multiplier.is_finite() { multiplier.max(1.0) } else { 1.0 };
+        self
+    }
+
+    /// Fraction (0.0-1.0) of each delay that may be randomly shaved off
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_nan() { 0.0 } else { jitter.clamp(0.0, 1.0) };
+        self
+    }
+
+    /// Number of delays handed out since creation or the last `reset`
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Start over from `base`, e.g. after a successful reconnect
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Delay to wait before the next attempt
+    pub fn next_delay(&mut self) -> Duration {
+        let nominal = self.nominal_delay(self.attempt);
+        self.attempt = self.attempt.saturating_add(1);
+
+        if self.jitter == 0.0 {
+            return nominal;
+        }
+        let shave = rand::thread_rng().gen_range(0.0..=self.jitter);
+        nominal.mul_f64(1.0 - shave)
+    }
+
+    fn nominal_delay(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        let secs = self.base.as_secs_f64() * self.multiplier.powi(exponent);
+        if !secs.is_finite() || secs >= self.max.as_secs_f64() {
+            self.max
+        } else {
+            Duration::from_secs_f64(secs)
+        }
+    }
+
+    /// Run `op` up to `max_attempts` times, sleeping between failures.
+    /// Returns the first success or the last error. Each call starts from
+    /// `base` regardless of this instance's current attempt count.
+    pub async fn retry_async<T, E, F, Fut>(&self, max_attempts: u32, mut op: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        let mut backoff = self.clone();
+        backoff.reset();
+        let max_attempts = max_attempts.max(1);
+
+        let mut attempt = 1;
+        loop {
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt >= max_attempts => return Err(e),
+                Err(e) => {
+                    let delay = backoff.next_delay();
+                    debug!("Attempt {}/{} failed: {}; retrying in {:?}", attempt, max_attempts, e, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Counters reported by [`TtlLruCache::metrics`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room for new ones
+    pub evictions: u64,
+    /// Entries dropped because their TTL ran out
+    pub expirations: u64,
+}
+
+/// Thread-safe LRU cache whose entries also expire `ttl` after insertion
+///
+/// Expired entries are removed lazily when looked up or by
+/// [`purge_expired`](Self::purge_expired), so `len` may include entries that
+/// have expired but not been touched since.
+#[derive(Debug)]
+pub struct TtlLruCache<K: Hash + Eq, V> {
+    entries: Mutex<LruCache<K, (V, Instant)>>,
+    ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+}
+
+impl<K: Hash + Eq, V: Clone> TtlLruCache<K, V> {
+    /// Cache holding at most `capacity` entries (minimum 1) for `ttl` each
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            expirations: AtomicU64::new(0),
+        }
+    }
+
+    /// Clone of the cached value, if present and not expired
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// Insert or replace `key`, evicting the least recently used entry if full
+    pub fn put(&self, key: K, value: V) {
+        self.put_at(key, value, Instant::now())
+    }
+
+    /// Remove `key`, returning its value if it was present and not expired
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let now = Instant::now();
+        self.entries
+            .lock()
+            .pop(key)
+            .and_then(|(value, inserted)| (now.duration_since(inserted) < self.ttl).then_some(value))
+    }
+
+    /// Drop every expired entry and return how many were removed
+    pub fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        let before = entries.len();
+        let mut kept = LruCache::new(entries.cap());
+        // Re-insert survivors oldest first so their recency order is preserved
+        while let Some((key, entry)) = entries.pop_lru() {
+            if now.duration_since(entry.1) < self.ttl {
+                kept.put(key, entry);
+            }
+        }
+        *entries = kept;
+        let removed = before - entries.len();
+        self.expirations.fetch_add(removed as u64, Ordering::Relaxed);
+        removed
+    }
+
+    /// Number of stored entries, including expired ones not yet purged
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// Snapshot of hit, miss, eviction and expiration counts
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+        }
+    }
+
+    fn get_at(&self, key: &K, now: Instant) -> Option<V> {
+        let mut entries = self.entries.lock();
+        let expired = match entries.get(key) {
+            Some((value, inserted)) if now.duration_since(*inserted) < self.ttl => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Some(value.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            entries.pop(key);
+            self.expirations.fetch_add(1, Ordering::Relaxed);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    fn put_at(&self, key: K, value: V, now: Instant) {
+        let mut entries = self.entries.lock();
+        let replacing = entries.contains(&key);
+        if let Some((_, (_, inserted))) = entries.push(key, (value, now)) {
+            if replacing {
+                return;
+            }
+            if now.duration_since(inserted) >= self.ttl {
+                self.expirations.fetch_add(1, Ordering::Relaxed);
+            } else {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    proptest! {
+        #[test]
+        fn delays_are_monotonic_up_to_the_cap(
+            base_ms in 1u64..1_000,
+            max_ms in 1u64..60_000,
+            multiplier in 1.0f64..4.0,
+        ) {
+            let max = Duration::from_millis(max_ms);
+            let mut backoff = Backoff::new(Duration::from_millis(base_ms), max).with_multiplier(multiplier);
+
+            let mut previous = Duration::ZERO;
+            for _ in 0..64 {
+                let delay = backoff.next_delay();
+                prop_assert!(delay >= previous);
+                prop_assert!(delay <= max.max(Duration::from_millis(base_ms)));
+                previous = delay;
+            }
+        }
+
+        #[test]
+        fn jittered_delays_stay_within_bounds(
+            base_ms in 1u64..1_000,
+            max_ms in 1u64..60_000,
+            jitter in 0.0f64..=1.0,
+        ) {
+            let max = Duration::from_millis(max_ms);
+            let mut backoff = Backoff::new(Duration::from_millis(base_ms), max).with_jitter(jitter);
+            let reference = Backoff::new(Duration::from_millis(base_ms), max);
+
+            for attempt in 0..32 {
+                let nominal = reference.nominal_delay(attempt);
+                let delay = backoff.next_delay();
+                prop_assert!(delay <= nominal);
+                prop_assert!(delay >= nominal.mul_f64(1.0 - jitter));
+            }
+        }
+    }
+
+    #[test]
+    fn reset_starts_over_from_base() {
+        let mut backoff = Backoff::new(Duration::from_millis(10), Duration::from_secs(1));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(10));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(20));
+        assert_eq!(backoff.attempt(), 2);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_millis(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_async_returns_first_success() {
+        let calls = AtomicU32::new(0);
+        let backoff = Backoff::new(Duration::from_millis(10), Duration::from_millis(100));
+        let started = tokio::time::Instant::now();
+
+        let result: Result<u32, String> = backoff
+            .retry_async(5, || {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                async move { if n < 3 { Err(format!("attempt {}", n)) } else { Ok(n) } }
+            })
+            .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // Slept 10ms then 20ms on the paused clock
+        assert!(started.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_async_gives_up_with_last_error() {
+        let calls = AtomicU32::new(0);
+        let backoff = Backoff::new(Duration::from_millis(10), Duration::from_millis(100));
+
+        let result: Result<(), String> = backoff
+            .retry_async(3, || {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                async move { Err(format!("attempt {}", n)) }
+            })
+            .await;
+
+        assert_eq!(result, Err("attempt 3".to_string()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_at_capacity() {
+        let cache = TtlLruCache::new(2, Duration::from_secs(60));
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+
+        cache.put("c", 3);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+
+        // Replacing an existing key is not an eviction
+        cache.put("c", 4);
+        assert_eq!(cache.get(&"c"), Some(4));
+        assert_eq!(
+            cache.metrics(),
+            CacheMetrics { hits: 4, misses: 1, evictions: 1, expirations: 0 }
+        );
+    }
+
+    #[test]
+    fn cache_entries_expire_after_ttl() {
+        let ttl = Duration::from_secs(30);
+        let cache = TtlLruCache::new(4, ttl);
+        let start = Instant::now();
+        cache.put_at("a", 1, start);
+        cache.put_at("b", 2, start + Duration::from_secs(20));
+
+        assert_eq!(cache.get_at(&"a", start + Duration::from_secs(29)), Some(1));
+        assert_eq!(cache.get_at(&"a", start + ttl), None);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get_at(&"b", start + ttl), Some(2));
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.expirations, 1);
+        assert_eq!(metrics.misses, 1);
+    }
+
+    #[test]
+    fn purge_expired_keeps_fresh_entries() {
+        let cache = TtlLruCache::new(4, Duration::from_secs(30));
+        let old = Instant::now() - Duration::from_secs(60);
+        cache.put_at(1, "stale", old);
+        cache.put(2, "fresh");
+
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&2), Some("fresh"));
+    }
+
+    #[test]
+    fn cache_is_safe_under_concurrent_access() {
+        let cache = std::sync::Arc::new(TtlLruCache::new(64, Duration::from_secs(60)));
+        let handles: Vec<_> = (0..8u64)
+            .map(|thread| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    for i in 0..1_000u64 {
+                        let key = (thread * 1_000 + i) % 128;
+                        cache.put(key, i);
+                        cache.get(&key);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(cache.len() <= 64);
+        let metrics = cache.metrics();
+        assert_eq!(metrics.hits + metrics.misses, 8_000);
+    }
+}
@@ -0,0 +1,68 @@
+//! `core-engine --validate-config` exits without starting the server.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn core_engine() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_core-engine"))
+}
+
+fn write_temp(contents: &str) -> tempfile::NamedTempFile {
+    let file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+    std::fs::write(file.path(), contents).unwrap();
+    file
+}
+
+#[test]
+fn shipped_agent_config_is_valid() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("config/agents.toml");
+    let output = core_engine().arg("--validate-config").arg(&path).output().unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("validation passed"));
+}
+
+#[test]
+fn invalid_agent_config_exits_non_zero() {
+    let file = write_temp(
+        r#"
+[global]
+default_interval_ms = 1000
+max_concurrent_agents = 0
+operation_timeout_seconds = 30
+enable_hot_reload = true
+validation_level = "strict"
+
+[[agents]]
+name = "Broken"
+enabled = true
+interval_ms = 0
+description = "interval of zero is rejected"
+version = "1.0.0"
+author = "ops"
+
+[agents.risk]
+max_drawdown = 0.05
+max_position_value = 1000
+leverage_limit = 1.0
+
+[agents.monitoring]
+enable_metrics = true
+log_level = "info"
+performance_tracking = true
+"#,
+    );
+
+    let output = core_engine().arg("--validate-config").arg(file.path()).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("INVALID_MAX_AGENTS"), "stderr: {}", stderr);
+    assert!(stderr.contains("INVALID_INTERVAL"), "stderr: {}", stderr);
+}
+
+#[test]
+fn missing_path_is_a_usage_error() {
+    let output = core_engine().arg("--validate-config").output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}